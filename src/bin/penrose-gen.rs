@@ -0,0 +1,303 @@
+//! Generate a Penrose patch without opening a window.
+//!
+//! A seed patch is deflated a number of times, or until it covers a box,
+//! converted to the chosen tile set and written as a patch file, an SVG or
+//! a PNG. Run with `--help` for the options.
+
+use std::process;
+
+use penrose::exact::ExactPoint;
+use penrose::*;
+
+const USAGE: &str = "\
+Usage: penrose-gen [options] <output>
+
+Options:
+  --tiles <set>       kite-dart, rhombus or robinson (default: the seed's set)
+  --seed <seed>       a vertex: sun, star, ace, deuce, jack, queen, king,
+                      five-big, five-small, four-big, three-big, one-big,
+                      blunt, three-small or one-small; or a single tile:
+                      kite, dart, fat, skinny, robinson-a, robinson-b
+                      (default: sun)
+  --depth <n>         deflate the seed n times (default: 4)
+  --bbox <x0,y0,x1,y1>
+                      deflate until the patch covers the box, and keep the
+                      tiles whose centres are inside it
+  --format <format>   json, svg or png (default: from the output name)
+  --scale <s>         pixels per unit for svg and png (default: 25)
+  --size <wxh>        png size in pixels (default: fits the patch)
+  --rotate <degrees>  turn the whole patch anticlockwise (default: 0)
+  --no-edges          leave out the tile edges
+  --no-arcs           leave out the arcs
+  --bars              draw the Ammann bars
+";
+
+// Deflations stop here when covering a box, at some millions of tiles.
+const MAX_DEPTH: usize = 16;
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum TileSet {
+    KiteDart,
+    Rhombus,
+    Robinson,
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Seed {
+    Vertex(VertexType),
+    RhombusVertex(RhombusVertexType),
+    Single(Tile),
+}
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+enum Format {
+    Json,
+    Svg,
+    Png,
+}
+
+struct Options {
+    tiles: Option<TileSet>,
+    seed: Seed,
+    depth: usize,
+    bbox: Option<BoundingBox>,
+    format: Option<Format>,
+    scale: f64,
+    size: Option<(usize, usize)>,
+    rotation: f64,
+    style: Style,
+    output: String,
+}
+
+// "FiveBig" is "five-big".
+fn kebab(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            result.push('-');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+fn parse_seed(s: &str) -> Option<Seed> {
+    VertexType::all().iter().find(|v| kebab(&format!("{:?}", v)) == s).map(|v| Seed::Vertex(*v))
+        .or_else(|| RhombusVertexType::all().iter().find(|v| kebab(&format!("{:?}", v)) == s).map(|v| Seed::RhombusVertex(*v)))
+        .or_else(|| Tile::all().iter().find(|t| t.name().to_lowercase().replace('_', "-") == s).map(|t| Seed::Single(*t)))
+}
+
+fn parse_tile_set(s: &str) -> Option<TileSet> {
+    match s {
+        "kite-dart" => Some(TileSet::KiteDart),
+        "rhombus" => Some(TileSet::Rhombus),
+        "robinson" => Some(TileSet::Robinson),
+        _ => None,
+    }
+}
+
+fn parse_format(s: &str) -> Option<Format> {
+    match s {
+        "json" => Some(Format::Json),
+        "svg" => Some(Format::Svg),
+        "png" => Some(Format::Png),
+        _ => None,
+    }
+}
+
+fn parse_bbox(s: &str) -> Option<BoundingBox> {
+    let v: Vec<f64> = s.split(',').map(|x| x.trim().parse().ok()).collect::<Option<_>>()?;
+    match v.as_slice() {
+        [x0, y0, x1, y1] if x0 < x1 && y0 < y1 => Some(BoundingBox::new((*x0, *y0), (*x1, *y1))),
+        _ => None,
+    }
+}
+
+fn parse_size(s: &str) -> Option<(usize, usize)> {
+    let mut parts = s.split('x');
+    let w = parts.next()?.parse().ok()?;
+    let h = parts.next()?.parse().ok()?;
+    match parts.next() {
+        None if w > 0 && h > 0 => Some((w, h)),
+        _ => None,
+    }
+}
+
+// The value after option `arg`, parsed by `parse`.
+fn value<'a, T>(args: &mut impl Iterator<Item = &'a String>, arg: &str, parse: impl Fn(&str) -> Option<T>)
+                -> Result<T, String> {
+    let v = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+    parse(v).ok_or_else(|| format!("bad value {} for {}", v, arg))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        tiles: None,
+        seed: Seed::Vertex(VertexType::Sun),
+        depth: 4,
+        bbox: None,
+        format: None,
+        scale: 25.,
+        size: None,
+        rotation: 0.,
+        style: Style::default(),
+        output: String::new(),
+    };
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tiles" => options.tiles = Some(value(&mut args, arg, parse_tile_set)?),
+            "--seed" => options.seed = value(&mut args, arg, parse_seed)?,
+            "--depth" => options.depth = value(&mut args, arg, |v| v.parse().ok())?,
+            "--bbox" => options.bbox = Some(value(&mut args, arg, parse_bbox)?),
+            "--format" => options.format = Some(value(&mut args, arg, parse_format)?),
+            "--scale" => options.scale = value(&mut args, arg, |v| v.parse().ok().filter(|s: &f64| *s > 0.))?,
+            "--size" => options.size = Some(value(&mut args, arg, parse_size)?),
+            "--rotate" => options.rotation = value(&mut args, arg, |v| v.parse().ok().filter(|r: &f64| r.is_finite()))?,
+            "--no-edges" => options.style.edge_weight = 0.,
+            "--no-arcs" => options.style.show_arcs = false,
+            "--bars" => options.style.show_bars = true,
+            "-h" | "--help" => return Err(String::new()),
+            a if a.starts_with('-') => return Err(format!("unknown option {}", a)),
+            a if output.is_none() => output = Some(a.to_string()),
+            a => return Err(format!("more than one output: {}", a)),
+        }
+    }
+    options.output = output.ok_or_else(|| "no output file".to_string())?;
+    Ok(options)
+}
+
+fn seed_tiles(seed: Seed) -> Vec<Box<dyn PenroseTile>> {
+    let origin = ExactPoint::origin();
+    match seed {
+        Seed::Vertex(v) => v.tiles_exact(origin, 0).unwrap(),
+        Seed::RhombusVertex(v) => v.tiles_exact(origin, 0).unwrap(),
+        Seed::Single(kind) => vec![new_exact_tile(kind, origin, 0)],
+    }
+}
+
+fn seed_tile_set(seed: Seed) -> TileSet {
+    match seed {
+        Seed::Vertex(_) => TileSet::KiteDart,
+        Seed::RhombusVertex(_) => TileSet::Rhombus,
+        Seed::Single(kind) if kind.is_triangle() => TileSet::Robinson,
+        Seed::Single(Tile::FAT) | Seed::Single(Tile::SKINNY) => TileSet::Rhombus,
+        Seed::Single(_) => TileSet::KiteDart,
+    }
+}
+
+fn convert_to(tiles: &[Box<dyn PenroseTile>], set: TileSet) -> Vec<Box<dyn PenroseTile>> {
+    match set {
+        TileSet::KiteDart => to_kites_and_darts(&from_triangles(tiles)),
+        TileSet::Rhombus => to_rhombi(&from_triangles(tiles)),
+        TileSet::Robinson => to_triangles(&from_triangles(tiles)),
+    }
+}
+
+// Whether every point of `region` is inside the patch: its corners are, and
+// no open edge of the patch comes into it.
+fn covers(tiles: &[Box<dyn PenroseTile>], region: &BoundingBox) -> bool {
+    let mesh = Mesh::from_tiles(tiles);
+    let edges = mesh.boundary_edges();
+    if edges.is_empty() {
+        return false
+    }
+    let inside = |p: (f64, f64)| {
+        // Crossings of the boundary by a ray to the right of p.
+        edges.iter().filter(|e| {
+            let (a, b) = e.end_points();
+            (a.1 > p.1) != (b.1 > p.1) && p.0 < a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0)
+        }).count() % 2 == 1
+    };
+    let corners = [region.min, (region.max.0, region.min.1), region.max, (region.min.0, region.max.1)];
+    corners.iter().all(|c| inside(*c)) && edges.iter().all(|e| {
+        let (a, b) = e.end_points();
+        !region.contains(a) && !region.contains(b) && !region.contains(e.center)
+    })
+}
+
+fn generate(options: &Options) -> Result<Vec<Box<dyn PenroseTile>>, String> {
+    let mut tiles = seed_tiles(options.seed);
+    match options.bbox {
+        Some(region) => {
+            let mut depth = 0;
+            while !covers(&tiles, &region) {
+                if depth == MAX_DEPTH {
+                    return Err(format!("the patch doesn't cover the box after {} deflations", MAX_DEPTH))
+                }
+                tiles = deflate(&tiles);
+                depth += 1;
+            }
+        }
+        None => tiles = deflate_n(&tiles, options.depth),
+    }
+    let mut tiles = convert_to(&tiles, options.tiles.unwrap_or_else(|| seed_tile_set(options.seed)));
+    if let Some(region) = options.bbox {
+        tiles.retain(|t| region.contains(t.center()));
+    }
+    Ok(tiles)
+}
+
+fn write(options: &Options, tiles: &[Box<dyn PenroseTile>]) -> Result<(), String> {
+    let format = match options.format {
+        Some(f) => f,
+        None => options.output.rsplit('.').next().and_then(parse_format)
+            .ok_or_else(|| format!("can't tell the format of {}; use --format", options.output))?,
+    };
+    let error = |e: &dyn std::fmt::Display| format!("{}: {}", options.output, e);
+    let transform = RigidTransform::rotation(options.rotation);
+    match format {
+        Format::Json => {
+            let view = ViewSettings {
+                scale: options.scale,
+                show_edges: options.style.edge_weight > 0.,
+                show_arcs: options.style.show_arcs,
+                show_bars: options.style.show_bars,
+                rotation: options.rotation,
+            };
+            save_patch(&options.output, tiles, &view).map_err(|e| error(&e))
+        }
+        Format::Svg => save_svg(&options.output, tiles, &transform, options.scale, &options.style)
+            .map_err(|e| error(&e)),
+        Format::Png => {
+            // The image is centred on the middle of the turned patch.
+            let bounds = match options.bbox {
+                Some(b) => BoundingBox::around_points(
+                    [b.min, (b.max.0, b.min.1), b.max, (b.min.0, b.max.1)].iter().map(|p| transform.apply(*p))),
+                None => BoundingBox::around_points(tiles.iter().flat_map(|t| transform.vertices(t.as_ref()))),
+            }.unwrap_or_else(|| BoundingBox::around((0., 0.), 0.));
+            let c = ((bounds.min.0 + bounds.max.0) / 2., (bounds.min.1 + bounds.max.1) / 2.);
+            let size = options.size.unwrap_or((
+                ((bounds.max.0 - bounds.min.0) * options.scale + 2. * SVG_MARGIN).ceil() as usize,
+                ((bounds.max.1 - bounds.min.1) * options.scale + 2. * SVG_MARGIN).ceil() as usize));
+            let centred = transform.then(&RigidTransform::translation(-c.0, -c.1));
+            save_png(&options.output, tiles, &centred, size.0, size.1, options.scale, &options.style)
+                .map_err(|e| error(&e))
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            if message.is_empty() {
+                print!("{}", USAGE);
+                return
+            }
+            eprintln!("penrose-gen: {}\n\n{}", message, USAGE);
+            process::exit(2)
+        }
+    };
+    let result = generate(&options).and_then(|tiles| {
+        write(&options, &tiles)?;
+        println!("Wrote {} tiles to {}", tiles.len(), options.output);
+        Ok(())
+    });
+    if let Err(message) = result {
+        eprintln!("penrose-gen: {}", message);
+        process::exit(1)
+    }
+}
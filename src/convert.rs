@@ -0,0 +1,193 @@
+//! Conversion between kite and dart (P2) and rhombus (P3) patches.
+//!
+//! Both tile sets are made of the same two golden triangles. A half-kite is
+//! half of a skinny rhombus at its head plus half of a fat rhombus along its
+//! long edge, and a half-dart is half of a fat rhombus. The two halves of
+//! each fat rhombus lie on either side of a long P2 edge. The rhombi have the
+//! short P2 edge length, so tiles keep their size and position.
+//!
+//! Going back, each skinny rhombus gives a kite. A dart is made from the
+//! halves of two fat rhombi that meet along its axis; darts cut by the border
+//! of a rhombus patch are left out.
+//!
+//! The half-kites and half-darts themselves are the Robinson triangles, so
+//! kites and darts are also split into, and joined from, `RobinsonA` and
+//! `RobinsonB` tiles.
+
+use crate::deflate::*;
+use crate::exact::Cyclotomic;
+use crate::tiles::*;
+
+// With the kite tail, kite head and dart side vertices coloured H and the
+// others T, the big arc of the fat rhombus across a long P2 edge is at its
+// T end, and the skinny rhombus in a kite has its big arc at the
+// point that cuts the kite's axis.
+
+fn rhombus_halves(h: &Half) -> Vec<Half> {
+    match h.kind {
+        Tile::KITE => {
+            let q = golden_cut(h.a, h.c);
+            vec![Half { kind: Tile::SKINNY, a: h.b, b: q, c: h.c },
+                 Half { kind: Tile::FAT, a: q, b: h.b, c: h.a }]
+        }
+        _ => vec![Half { kind: Tile::FAT, a: h.a, b: h.c, c: h.b }],
+    }
+}
+
+fn kite_dart_halves(h: &Half) -> Vec<Half> {
+    match h.kind {
+        Tile::SKINNY => {
+            // The kite tail is phi^2 from the head, through the big arc vertex.
+            let phi2 = Cyclotomic::phi() + Cyclotomic::ONE;
+            let tail = h.c + (h.b - h.c).scale(phi2);
+            vec![Half { kind: Tile::KITE, a: tail, b: h.a, c: h.c }]
+        }
+        _ => vec![Half { kind: Tile::DART, a: h.a, b: h.c, c: h.b }],
+    }
+}
+
+// Replace the tiles of kinds `from` by the tiles `table` gives for them,
+// keeping tiles made of at least `min_halves` halves. Other tiles are copied.
+fn convert(tiles: &[Box<dyn PenroseTile>], from: &[Tile], table: impl Fn(Tile) -> Vec<Child>, min_halves: u8)
+           -> Vec<Box<dyn PenroseTile>> {
+    let tables: Vec<Vec<Child>> = from.iter().map(|k| table(*k)).collect();
+
+    let mut lookup = TileLookup::new();
+    let mut result: Vec<(Box<dyn PenroseTile>, u8)> = Vec::new();
+    for t in tiles {
+        let pieces = match from.iter().position(|k| *k == t.kind()) {
+            Some(i) => tables[i].iter()
+                .map(|c| (place_child(t.center(), t.exact_center(), t.angle(), t.reflected(), c), c.halves))
+                .collect(),
+            None => vec![(copy_tile(t.as_ref()), 2)],
+        };
+        for (piece, halves) in pieces {
+            match lookup.get(piece.as_ref()) {
+                Some(i) => result[i].1 += halves,
+                None => {
+                    lookup.insert(piece.as_ref(), result.len());
+                    result.push((piece, halves));
+                }
+            }
+        }
+    }
+    result.into_iter().filter(|(_, h)| *h >= min_halves).map(|(t, _)| t).collect()
+}
+
+/// Convert the kites and darts of a patch to fat and skinny rhombi.
+///
+/// Fat rhombi that are only half covered by the patch are completed.
+pub fn to_rhombi(tiles: &[Box<dyn PenroseTile>]) -> Vec<Box<dyn PenroseTile>> {
+    convert(tiles, &[Tile::KITE, Tile::DART], |k| merge_halves(k, rhombus_halves), 1)
+}
+
+/// Convert the fat and skinny rhombi of a patch to kites and darts.
+///
+/// `to_kites_and_darts(&to_rhombi(x))` gives back the tiles of `x`.
+pub fn to_kites_and_darts(tiles: &[Box<dyn PenroseTile>]) -> Vec<Box<dyn PenroseTile>> {
+    convert(tiles, &[Tile::FAT, Tile::SKINNY], |k| merge_halves(k, kite_dart_halves), 2)
+}
+
+/// Split the kites and darts of a patch into Robinson triangles. Rhombi are
+/// converted to kites and darts first.
+pub fn to_triangles(tiles: &[Box<dyn PenroseTile>]) -> Vec<Box<dyn PenroseTile>> {
+    let table = |k| halves(k).iter().map(|h| {
+        let (kind, center, angle) = triangle(h);
+        Child { kind, center, angle, halves: 2 }
+    }).collect();
+    convert(&to_kites_and_darts(tiles), &[Tile::KITE, Tile::DART], table, 2)
+}
+
+/// Join the Robinson triangles of a patch into kites and darts.
+///
+/// Triangles whose other half is missing are completed.
+pub fn from_triangles(tiles: &[Box<dyn PenroseTile>]) -> Vec<Box<dyn PenroseTile>> {
+    let from = [Tile::ROBINSON_A, Tile::ROBINSON_A_MIRROR, Tile::ROBINSON_B, Tile::ROBINSON_B_MIRROR];
+    convert(tiles, &from, |k| merge_halves(k, |h| vec![*h]), 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+    use crate::exact::ExactPoint;
+    use std::collections::HashSet;
+
+    fn keys(tiles: &[Box<dyn PenroseTile>]) -> HashSet<(Tile, i32, Option<ExactPoint>)> {
+        tiles.iter().map(|t| (t.kind(), t.angle(), t.exact_center())).collect()
+    }
+
+    #[test]
+    fn test_to_rhombi() {
+        for seed in [sun(), star()].iter() {
+            let tiles = deflate_n(seed, 3);
+            let rhombi = to_rhombi(&tiles);
+            assert_eq!(count(&rhombi, Tile::SKINNY), count(&tiles, Tile::KITE));
+            assert_eq!(count(&rhombi, Tile::KITE) + count(&rhombi, Tile::DART), 0);
+            check_rhombus_arcs(&rhombi);
+            check_no_overlaps(&rhombi);
+
+            assert_eq!(keys(&to_kites_and_darts(&rhombi)), keys(&tiles));
+        }
+    }
+
+    #[test]
+    fn test_to_kites_and_darts() {
+        for seed in [Tile::FAT, Tile::SKINNY].iter() {
+            let tiles = deflate_n(&[new_exact_tile(*seed, ExactPoint::origin(), 0)], 4);
+            let p2 = to_kites_and_darts(&tiles);
+            assert_eq!(count(&p2, Tile::KITE), count(&tiles, Tile::SKINNY));
+            assert!(count(&p2, Tile::DART) > 0);
+            check_vertex_colours(&p2);
+            check_no_overlaps(&p2);
+
+            // Converting back loses the darts cut by the border and adds
+            // rhombi from kites reaching outside it, but agrees with the
+            // original tiles where they overlap.
+            let back = to_rhombi(&p2);
+            let common = keys(&back).intersection(&keys(&tiles)).count();
+            assert!(common * 10 > tiles.len() * 9);
+            let mut both: Vec<Box<dyn PenroseTile>> = back.iter().map(|t| copy_tile(t.as_ref())).collect();
+            let seen = keys(&back);
+            both.extend(tiles.iter().filter(|t| !seen.contains(&(t.kind(), t.angle(), t.exact_center())))
+                        .map(|t| copy_tile(t.as_ref())));
+            check_rhombus_arcs(&both);
+            check_no_overlaps(&both);
+        }
+    }
+
+    #[test]
+    fn test_triangles() {
+        let tiles = deflate_n(&sun(), 3);
+        let triangles = to_triangles(&tiles);
+        assert_eq!(triangles.len(), 2 * tiles.len());
+        assert_eq!(count(&triangles, Tile::ROBINSON_A) + count(&triangles, Tile::ROBINSON_A_MIRROR),
+                   2 * count(&tiles, Tile::KITE));
+        assert_eq!(count(&triangles, Tile::ROBINSON_A), count(&triangles, Tile::ROBINSON_A_MIRROR));
+        assert_eq!(count(&triangles, Tile::ROBINSON_B), count(&triangles, Tile::ROBINSON_B_MIRROR));
+        check_no_overlaps(&triangles);
+        assert_eq!(keys(&from_triangles(&triangles)), keys(&tiles));
+
+        // A lone triangle is completed to its whole tile.
+        let half = vec![new_exact_tile(Tile::ROBINSON_B_MIRROR, ExactPoint::origin(), 36)];
+        assert_eq!(keys(&from_triangles(&half)), keys(&[new_exact_tile(Tile::DART, ExactPoint::origin(), 36)]));
+    }
+
+    #[test]
+    fn test_convert_float() {
+        let tiles: Vec<Box<dyn PenroseTile>> = deflate_n(&sun(), 2).iter()
+            .map(|t| new_tile(t.kind(), t.center().0, t.center().1, t.angle()))
+            .collect();
+        let exact = to_rhombi(&deflate_n(&sun(), 2));
+        let float = to_rhombi(&tiles);
+        assert_eq!(exact.len(), float.len());
+        for (e, f) in exact.iter().zip(float.iter()) {
+            assert!(f.exact_center().is_none());
+            assert_eq!(e.kind(), f.kind());
+            assert_eq!(e.angle(), f.angle());
+            assert!((e.center().0 - f.center().0).abs() < 1e-9);
+            assert!((e.center().1 - f.center().1).abs() < 1e-9);
+        }
+        assert_eq!(to_kites_and_darts(&float).len(), tiles.len());
+    }
+}
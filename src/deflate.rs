@@ -0,0 +1,523 @@
+//! Deflation (substitution) of patches.
+//!
+//! Each tile is cut into its two Robinson half-tiles, every half-tile is
+//! replaced by smaller half-tiles, and the pieces are merged back into whole
+//! tiles. Half-tiles at the edge of the patch are completed, which is always
+//! legal because their mirror halves exist in the full tiling.
+//!
+//! The tile structs have a fixed size, so instead of shrinking the new tiles
+//! by 1/phi the result is returned at the original tile size and the patch
+//! grows by a factor of phi.
+
+use std::collections::HashMap;
+
+use crate::exact::{Cyclotomic, ExactPoint};
+use crate::tiles::*;
+
+// A Robinson half-tile.
+//
+// Kites and darts are split along their axis of symmetry: `a` and `c` are the
+// ends of the axis and `b` the remaining vertex. For a half-kite `a` is the
+// tail (72 degree vertex) and `c` the head (144 degree vertex). For a
+// half-dart `a` is the concave vertex and `c` the tip.
+//
+// Rhombi are split along the diagonal between the vertices carrying the
+// arcs: `a` is the apex opposite that diagonal, and `b` and `c` are its ends,
+// `b` being the vertex with the big arc.
+#[derive(Clone, Copy)]
+pub(crate) struct Half {
+    pub kind: Tile,
+    pub a: ExactPoint,
+    pub b: ExactPoint,
+    pub c: ExactPoint,
+}
+
+pub(crate) fn halves(kind: Tile) -> Vec<Half> {
+    let v = exact_local_vertices(kind);
+    match kind {
+        Tile::FAT => vec![Half { kind, a: v[1], b: v[0], c: v[2] },
+                          Half { kind, a: v[3], b: v[0], c: v[2] }],
+        Tile::SKINNY => vec![Half { kind, a: v[0], b: v[1], c: v[3] },
+                             Half { kind, a: v[2], b: v[1], c: v[3] }],
+        Tile::ROBINSON_A | Tile::ROBINSON_A_MIRROR | Tile::ROBINSON_B | Tile::ROBINSON_B_MIRROR => {
+            let (whole, mirrored) = robinson_parent(kind);
+            let (b, c) = if mirrored { (v[2], v[1]) } else { (v[1], v[2]) };
+            vec![Half { kind: whole, a: v[0], b, c }]
+        }
+        _ => vec![Half { kind, a: v[0], b: v[1], c: v[2] },
+                  Half { kind, a: v[0], b: v[3], c: v[2] }],
+    }
+}
+
+// Point on p-q at 1/phi of the way from p.
+pub(crate) fn golden_cut(p: ExactPoint, q: ExactPoint) -> ExactPoint {
+    p + (q - p).scale(Cyclotomic::inv_phi())
+}
+
+// The P2 and P3 half-tile substitution rules, at 1/phi of the original size.
+fn split(h: &Half) -> Vec<Half> {
+    match h.kind {
+        Tile::KITE => {
+            let d = golden_cut(h.a, h.c);
+            let e = golden_cut(h.b, h.a);
+            vec![Half { kind: Tile::KITE, a: h.b, b: h.c, c: d },
+                 Half { kind: Tile::KITE, a: h.b, b: e, c: d },
+                 Half { kind: Tile::DART, a: e, b: d, c: h.a }]
+        }
+        Tile::DART => {
+            let e = golden_cut(h.c, h.b);
+            vec![Half { kind: Tile::KITE, a: h.c, b: e, c: h.a },
+                 Half { kind: Tile::DART, a: e, b: h.a, c: h.b }]
+        }
+        Tile::SKINNY => {
+            let p = golden_cut(h.a, h.b);
+            vec![Half { kind: Tile::SKINNY, a: h.c, b: p, c: h.b },
+                 Half { kind: Tile::FAT, a: p, b: h.c, c: h.a }]
+        }
+        Tile::FAT => {
+            let q = golden_cut(h.b, h.a);
+            let r = golden_cut(h.b, h.c);
+            vec![Half { kind: Tile::FAT, a: r, b: h.c, c: h.a },
+                 Half { kind: Tile::FAT, a: q, b: r, c: h.b },
+                 Half { kind: Tile::SKINNY, a: r, b: q, c: h.a }]
+        }
+        _ => unreachable!("half-tiles are halves of kites, darts and rhombi"),
+    }
+}
+
+// Direction of an exact vector that lies along one of the ten lattice
+// directions, in degrees.
+fn direction(v: ExactPoint) -> i32 {
+    let (x, y) = v.to_f64();
+    let a = (y.atan2(x) * 180. / std::f64::consts::PI / 36.).round() as i32;
+    (a * 36).rem_euclid(360)
+}
+
+// Centre and angle of the whole tile a half-tile belongs to.
+pub(crate) fn whole(h: &Half) -> (Tile, ExactPoint, i32) {
+    match h.kind {
+        Tile::KITE => (h.kind, golden_cut(h.a, h.c), direction(h.c - h.a)),
+        Tile::DART => (h.kind, h.a, direction(h.c - h.a)),
+        Tile::FAT => (h.kind, h.b.midpoint(&h.c), direction(h.c - h.b)),
+        Tile::SKINNY => {
+            // The apex is vertex 0 if the vertices run anticlockwise from it,
+            // otherwise vertex 0 is its mirror image across the diagonal.
+            let (x1, y1) = (h.b - h.a).to_f64();
+            let (x3, y3) = (h.c - h.a).to_f64();
+            let v0 = if x1*y3 - y1*x3 > 0. { h.a } else { h.b + h.c - h.a };
+            (h.kind, h.b.midpoint(&h.c), direction(h.b - v0))
+        }
+        _ => unreachable!("half-tiles are halves of kites, darts and rhombi"),
+    }
+}
+
+// The Robinson triangle a half-kite or half-dart is, with its centre and
+// angle. The triangle is mirrored if `b` is to the left of `a` to `c`.
+pub(crate) fn triangle(h: &Half) -> (Tile, ExactPoint, i32) {
+    let (kind, center, angle) = whole(h);
+    let (x1, y1) = (h.c - h.a).to_f64();
+    let (x2, y2) = (h.b - h.a).to_f64();
+    let mirrored = x1*y2 - y1*x2 > 0.;
+    let kind = match (kind, mirrored) {
+        (Tile::KITE, false) => Tile::ROBINSON_A,
+        (Tile::KITE, true) => Tile::ROBINSON_A_MIRROR,
+        (Tile::DART, false) => Tile::ROBINSON_B,
+        _ => Tile::ROBINSON_B_MIRROR,
+    };
+    (kind, center, angle)
+}
+
+// A tile that replaces a `kind` tile at the origin with angle 0, at the
+// original tile size. `halves` is 2 if the child lies wholly inside its
+// parent and 1 if only one of its half-tiles does.
+pub(crate) struct Child {
+    pub kind: Tile,
+    pub center: ExactPoint,
+    pub angle: i32,
+    pub halves: u8,
+}
+
+// Replace each half of a `kind` tile at the origin with the half-tiles given
+// by `f`, and merge those back into whole tiles.
+pub(crate) fn merge_halves(kind: Tile, f: impl Fn(&Half) -> Vec<Half>) -> Vec<Child> {
+    let mut result: Vec<Child> = Vec::new();
+    for h in halves(kind) {
+        for s in f(&h) {
+            let (k, c, a) = whole(&s);
+            match result.iter_mut().find(|r| r.kind == k && r.center == c && r.angle == a) {
+                Some(r) => r.halves += 1,
+                None => result.push(Child { kind: k, center: c, angle: a, halves: 1 }),
+            }
+        }
+    }
+    result
+}
+
+pub(crate) fn children(kind: Tile) -> Vec<Child> {
+    // Robinson triangles are not merged: each piece is a whole triangle.
+    let mut result = if kind.is_triangle() {
+        halves(kind).iter().flat_map(split).map(|s| {
+            let (kind, center, angle) = triangle(&s);
+            Child { kind, center, angle, halves: 2 }
+        }).collect()
+    } else {
+        merge_halves(kind, split)
+    };
+    for c in result.iter_mut() {
+        c.center = c.center.scale(Cyclotomic::phi());
+    }
+    result
+}
+
+// Place a child relative to a parent with the given centre and angle. A
+// reflected parent holds the mirror images of its children.
+pub(crate) fn place_child(center: (f64, f64), exact: Option<ExactPoint>, angle: i32, reflected: bool, c: &Child)
+                          -> Box<dyn PenroseTile> {
+    if reflected {
+        let mirrored = Child { kind: c.kind, center: c.center.conj(), angle: (360 - c.angle) % 360, halves: c.halves };
+        return reflect_tile(place_child(center, exact, angle, false, &mirrored).as_ref())
+    }
+    let child_angle = (angle + c.angle) % 360;
+    match offset_center(center, exact, c.center, angle) {
+        (_, Some(p)) => new_exact_tile(c.kind, p, child_angle),
+        ((x, y), None) => new_tile(c.kind, x, y, child_angle),
+    }
+}
+
+// Finds tiles by kind, angle and centre: exactly for tiles with exact
+// centres, otherwise to within a small tolerance. A reflected tile is found
+// as the unreflected tile that looks the same, so a patch with tiles both
+// ways round still shares the children on their common edges.
+pub(crate) struct TileLookup {
+    exact: HashMap<(Tile, i32, ExactPoint), usize>,
+    approx: HashMap<(Tile, i32, i64, i64), Vec<ApproxEntry>>,
+}
+
+type ApproxEntry = ((f64, f64), usize);
+
+impl TileLookup {
+    pub fn new() -> Self {
+        TileLookup { exact: HashMap::new(), approx: HashMap::new() }
+    }
+
+    // The angle of the unreflected tile that looks like `t`: kites, darts and
+    // fat rhombi are symmetric, and a skinny rhombus turns over onto itself
+    // turned by 144 degrees.
+    fn angle(t: &dyn PenroseTile) -> i32 {
+        if t.reflected() && t.kind() == Tile::SKINNY { (t.angle() + 144) % 360 } else { t.angle() }
+    }
+
+    fn cell(p: (f64, f64)) -> (i64, i64) {
+        ((p.0 * 1e3).round() as i64, (p.1 * 1e3).round() as i64)
+    }
+
+    pub fn get(&self, t: &dyn PenroseTile) -> Option<usize> {
+        if let Some(p) = t.exact_center() {
+            return self.exact.get(&(t.kind(), TileLookup::angle(t), p)).copied()
+        }
+        let c = t.center();
+        let (x, y) = TileLookup::cell(c);
+        for dx in -1..2 {
+            for dy in -1..2 {
+                if let Some(v) = self.approx.get(&(t.kind(), TileLookup::angle(t), x + dx, y + dy)) {
+                    for (p, i) in v {
+                        if (p.0 - c.0).abs() < 1e-6 && (p.1 - c.1).abs() < 1e-6 {
+                            return Some(*i)
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // Returns false, leaving the lookup unchanged, if the tile is already in it.
+    pub fn insert(&mut self, t: &dyn PenroseTile, i: usize) -> bool {
+        if self.get(t).is_some() {
+            return false
+        }
+        match t.exact_center() {
+            Some(p) => { self.exact.insert((t.kind(), TileLookup::angle(t), p), i); }
+            None => {
+                let (x, y) = TileLookup::cell(t.center());
+                self.approx.entry((t.kind(), TileLookup::angle(t), x, y)).or_default().push((t.center(), i));
+            }
+        }
+        true
+    }
+}
+
+// Rotate `offset` by `angle` and add it to `center`, exactly if possible.
+pub(crate) fn offset_center(center: (f64, f64), exact: Option<ExactPoint>, offset: ExactPoint, angle: i32)
+                            -> ((f64, f64), Option<ExactPoint>) {
+    match (exact, offset.rotate(angle)) {
+        (Some(c), Some(o)) => ((c + o).to_f64(), Some(c + o)),
+        _ => {
+            let (ox, oy) = offset.to_f64();
+            let r = angle as f64 * std::f64::consts::PI / 180.;
+            ((center.0 + r.cos()*ox - r.sin()*oy,
+              center.1 + r.sin()*ox + r.cos()*oy), None)
+        }
+    }
+}
+
+/// The tiles that replace `t`, each with the number of its half-tiles (1 or
+/// 2) that come from `t`.
+pub fn substitute(t: &dyn PenroseTile) -> Vec<(Box<dyn PenroseTile>, u8)> {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+
+    let (cx, cy) = t.center();
+    let exact = t.exact_center().map(|c| c.scale(Cyclotomic::phi()));
+    children(t.kind()).iter()
+        .map(|c| (place_child((phi*cx, phi*cy), exact, t.angle(), t.reflected(), c), c.halves))
+        .collect()
+}
+
+/// Replace every tile with its substitution: kites and darts by the P2
+/// rules, fat and skinny rhombi by the P3 rules, and Robinson triangles by
+/// the P2 rules for the halves they are.
+///
+/// Tiles that overlap after substitution (the halves shared by neighbouring
+/// tiles) are only returned once. Tiles with exact centres produce tiles with
+/// exact centres.
+pub fn deflate(tiles: &[Box<dyn PenroseTile>]) -> Vec<Box<dyn PenroseTile>> {
+    let mut seen = TileLookup::new();
+    let mut result = Vec::new();
+    for t in tiles {
+        for (child, _) in substitute(t.as_ref()) {
+            if seen.insert(child.as_ref(), result.len()) {
+                result.push(child);
+            }
+        }
+    }
+    result
+}
+
+/// Deflate `n` times.
+pub fn deflate_n(tiles: &[Box<dyn PenroseTile>], n: usize) -> Vec<Box<dyn PenroseTile>> {
+    let mut result: Vec<Box<dyn PenroseTile>> = tiles.iter().map(|t| copy_tile(t.as_ref())).collect();
+    for _ in 0..n {
+        result = deflate(&result);
+    }
+    result
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::convert::*;
+
+    pub(crate) fn sun() -> Vec<Box<dyn PenroseTile>> {
+        (0..5).map(|i| -> Box<dyn PenroseTile> {
+            Box::new(Kite::new_exact(ExactPoint::new(Cyclotomic::phi() * Cyclotomic::zeta_pow(2*i)), 72*i))
+        }).collect()
+    }
+
+    pub(crate) fn star() -> Vec<Box<dyn PenroseTile>> {
+        (0..5).map(|i| -> Box<dyn PenroseTile> {
+            Box::new(Dart::new_exact(ExactPoint::new(-Cyclotomic::phi() * Cyclotomic::zeta_pow(2*i)), 72*i))
+        }).collect()
+    }
+
+    pub(crate) fn count(tiles: &[Box<dyn PenroseTile>], kind: Tile) -> usize {
+        tiles.iter().filter(|t| t.kind() == kind).count()
+    }
+
+    // Each vertex of a legal kite and dart patch gets one of two colours, as
+    // shown by the arcs: kite tails and heads and dart side vertices are one
+    // colour, the other vertices the other.
+    pub(crate) fn check_vertex_colours(tiles: &[Box<dyn PenroseTile>]) {
+        let mut colours = HashMap::new();
+        for t in tiles {
+            let pattern = match t.kind() {
+                Tile::KITE => [true, false, true, false],
+                _ => [false, true, false, true],
+            };
+            for (v, c) in t.exact_vertices().unwrap().iter().zip(pattern.iter()) {
+                assert_eq!(colours.entry(*v).or_insert(*c), c);
+            }
+        }
+    }
+
+    // No two tiles overlap: no tile's centre of area lies inside another.
+    pub(crate) fn check_no_overlaps(tiles: &[Box<dyn PenroseTile>]) {
+        let inside = |p: (f64, f64), tri: &[(f64, f64)]| {
+            let cross = |a: (f64, f64), b: (f64, f64)| (b.0-a.0)*(p.1-a.1) - (b.1-a.1)*(p.0-a.0);
+            let s = [cross(tri[0], tri[1]), cross(tri[1], tri[2]), cross(tri[2], tri[0])];
+            s.iter().all(|x| *x > 1e-9) || s.iter().all(|x| *x < -1e-9)
+        };
+        let triangles: Vec<Vec<(f64, f64)>> = tiles.iter().flat_map(|t| {
+            let v = t.vertices();
+            (1..v.len()-1).map(|i| vec![v[0], v[i], v[i+1]]).collect::<Vec<_>>()
+        }).collect();
+        for (i, t1) in triangles.iter().enumerate() {
+            let c = ((t1[0].0 + t1[1].0 + t1[2].0) / 3., (t1[0].1 + t1[1].1 + t1[2].1) / 3.);
+            for (j, t2) in triangles.iter().enumerate() {
+                assert!(i == j || !inside(c, t2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_children() {
+        let kinds = |k| children(k).iter().map(|c| c.kind).collect::<Vec<Tile>>();
+        assert_eq!(kinds(Tile::KITE), vec![Tile::KITE, Tile::DART, Tile::KITE, Tile::DART]);
+        assert_eq!(kinds(Tile::DART), vec![Tile::KITE, Tile::DART, Tile::DART]);
+        assert_eq!(kinds(Tile::FAT), vec![Tile::FAT, Tile::FAT, Tile::SKINNY, Tile::FAT, Tile::SKINNY]);
+        assert_eq!(kinds(Tile::SKINNY), vec![Tile::SKINNY, Tile::FAT, Tile::SKINNY, Tile::FAT]);
+
+        let halves = |k| children(k).iter().map(|c| c.halves).collect::<Vec<u8>>();
+        assert_eq!(halves(Tile::KITE), vec![2, 1, 2, 1]);
+        assert_eq!(halves(Tile::DART), vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_deflate_sun() {
+        let expected = [(10, 5), (25, 20), (70, 55)];
+        let mut tiles = sun();
+        for (kites, darts) in expected.iter() {
+            tiles = deflate(&tiles);
+            assert_eq!(count(&tiles, Tile::KITE), *kites);
+            assert_eq!(count(&tiles, Tile::DART), *darts);
+            check_vertex_colours(&tiles);
+            check_no_overlaps(&tiles);
+        }
+    }
+
+    #[test]
+    fn test_deflate_star() {
+        let expected = [(5, 5), (15, 15), (45, 40)];
+        let mut tiles = star();
+        for (kites, darts) in expected.iter() {
+            tiles = deflate(&tiles);
+            assert_eq!(count(&tiles, Tile::KITE), *kites);
+            assert_eq!(count(&tiles, Tile::DART), *darts);
+            check_vertex_colours(&tiles);
+            check_no_overlaps(&tiles);
+        }
+    }
+
+    // Rhombus edges carry one of two arcs, which crosses the edge nearer one
+    // of its ends. Tiles sharing an edge must agree on both.
+    pub(crate) fn check_rhombus_arcs(tiles: &[Box<dyn PenroseTile>]) {
+        let mut decorations = HashMap::new();
+        for t in tiles {
+            // (big arc, nearest vertex) for edges 1 to 4
+            let near = match t.kind() {
+                Tile::FAT => [(true, 1), (false, 2), (false, 2), (true, 3)],
+                _ => [(true, 1), (true, 1), (false, 3), (false, 3)],
+            };
+            let v = t.exact_vertices().unwrap();
+            for (i, (colour, vi)) in near.iter().enumerate() {
+                let (p, q) = (v[i], v[(i+1)%4]);
+                let key = if p.to_f64() < q.to_f64() { (p, q) } else { (q, p) };
+                assert_eq!(decorations.entry(key).or_insert((*colour, v[*vi])), &(*colour, v[*vi]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deflate_rhombi() {
+        let expected = [(Tile::FAT, [(3, 2), (11, 7), (34, 23)]),
+                        (Tile::SKINNY, [(2, 2), (9, 6), (29, 19)])];
+        for (seed, counts) in expected.iter() {
+            let mut tiles = vec![new_exact_tile(*seed, ExactPoint::origin(), 0)];
+            for (fat, skinny) in counts.iter() {
+                tiles = deflate(&tiles);
+                assert_eq!(count(&tiles, Tile::FAT), *fat);
+                assert_eq!(count(&tiles, Tile::SKINNY), *skinny);
+                check_rhombus_arcs(&tiles);
+                check_no_overlaps(&tiles);
+            }
+            assert_eq!(deflate_n(&[new_exact_tile(*seed, ExactPoint::origin(), 0)], 3).len(), tiles.len());
+        }
+    }
+
+    #[test]
+    fn test_deflate_triangles() {
+        // Deflating the triangles of a patch deflates the patch itself, up
+        // to the half-tiles that deflating kites and darts completes.
+        let keys = |tiles: &[Box<dyn PenroseTile>]| -> std::collections::HashSet<_> {
+            tiles.iter().map(|t| (t.kind(), t.angle(), t.exact_center())).collect()
+        };
+        for seed in [sun(), star()].iter() {
+            for n in 0..3 {
+                let tiles = deflate_n(seed, n);
+                let triangles = deflate(&to_triangles(&tiles));
+                assert!(triangles.iter().all(|t| t.kind().is_triangle()));
+                check_no_overlaps(&triangles);
+                assert_eq!(keys(&from_triangles(&triangles)), keys(&deflate(&tiles)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deflate_reflected() {
+        // Deflating a mirrored tile gives the mirror image of its deflation.
+        let keys = |tiles: &[Box<dyn PenroseTile>], mirror: bool| -> std::collections::HashSet<_> {
+            tiles.iter().map(|t| {
+                let (c, angle) = (t.exact_center().unwrap(), t.angle());
+                if mirror { (t.kind(), (360 - angle) % 360, c.conj(), !t.reflected()) }
+                else { (t.kind(), angle, c, t.reflected()) }
+            }).collect()
+        };
+        for seed in [Tile::KITE, Tile::DART, Tile::FAT, Tile::SKINNY].iter() {
+            let tile = new_exact_tile(*seed, ExactPoint::origin(), 0);
+            let tiles = deflate_n(&[copy_tile(tile.as_ref())], 3);
+            let mirrored = deflate_n(&[reflect_tile(tile.as_ref())], 3);
+            assert!(mirrored.iter().all(|t| t.reflected()));
+            check_no_overlaps(&mirrored);
+            assert_eq!(keys(&mirrored, false), keys(&tiles, true));
+        }
+    }
+
+    #[test]
+    fn test_deflate_mixed_reflection() {
+        // Every tile looks like a reflected one, and a patch with some of its
+        // tiles swapped for those deflates to as many tiles, each once.
+        let look_alike = |t: &dyn PenroseTile| {
+            let angle = if t.kind() == Tile::SKINNY { (t.angle() + 216) % 360 } else { t.angle() };
+            reflect_tile(new_exact_tile(t.kind(), t.exact_center().unwrap(), angle).as_ref())
+        };
+        for seed in [sun(), deflate_n(&[new_exact_tile(Tile::FAT, ExactPoint::origin(), 0)], 2)].iter() {
+            let mixed: Vec<Box<dyn PenroseTile>> = seed.iter().enumerate()
+                .map(|(i, t)| if i % 2 == 0 { look_alike(t.as_ref()) } else { copy_tile(t.as_ref()) })
+                .collect();
+            let tiles = deflate_n(seed, 2);
+            let deflated = deflate_n(&mixed, 2);
+            assert_eq!(deflated.len(), tiles.len());
+            assert!(deflated.iter().any(|t| t.reflected()) && deflated.iter().any(|t| !t.reflected()));
+            check_no_overlaps(&deflated);
+        }
+
+        // The look-alike has the same bars, and is found as the same tile.
+        for angle in (0..10).map(|i| 36 * i) {
+            let skinny = new_exact_tile(Tile::SKINNY, ExactPoint::origin(), angle);
+            let mirrored = look_alike(skinny.as_ref());
+            let bars = mirrored.get_ammann_bars();
+            assert!(skinny.get_ammann_bars().iter().all(|p| bars.iter().any(|q| (p.0 - q.0).hypot(p.1 - q.1) < 1e-9)));
+            let mut lookup = TileLookup::new();
+            assert!(lookup.insert(skinny.as_ref(), 0));
+            assert_eq!(lookup.get(mirrored.as_ref()), Some(0));
+        }
+    }
+
+    #[test]
+    fn test_deflate_float_matches_exact() {
+        let exact = deflate(&deflate(&sun()));
+        let float: Vec<Box<dyn PenroseTile>> = sun().iter()
+            .map(|t| new_tile(t.kind(), t.center().0, t.center().1, t.angle()))
+            .collect();
+        let float = deflate(&deflate(&float));
+        assert_eq!(exact.len(), float.len());
+        for (e, f) in exact.iter().zip(float.iter()) {
+            assert!(e.exact_center().is_some());
+            assert!(f.exact_center().is_none());
+            assert_eq!(e.kind(), f.kind());
+            assert_eq!(e.angle(), f.angle());
+            assert!((e.center().0 - f.center().0).abs() < 1e-9);
+            assert!((e.center().1 - f.center().1).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Exact coordinates in the cyclotomic ring Z[ζ], where ζ = e^(iπ/5) is a
+//! primitive tenth root of unity.
+//!
+//! Every vertex of a Penrose tiling whose tiles are rotated by multiples of
+//! 36° lies on an integer combination of the tenth roots of unity, so
+//! positions can be stored and compared without any rounding.
+
+use std::ops::{Add, Sub, Neg, Mul};
+
+/// An element `c[0] + c[1]ζ + c[2]ζ² + c[3]ζ³` of Z[ζ].
+///
+/// 1, ζ, ζ², ζ³ form a basis, because ζ⁴ = ζ³ - ζ² + ζ - 1.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct Cyclotomic {
+    pub c: [i64; 4],
+}
+
+impl Cyclotomic {
+    pub const ZERO: Cyclotomic = Cyclotomic { c: [0, 0, 0, 0] };
+    pub const ONE: Cyclotomic = Cyclotomic { c: [1, 0, 0, 0] };
+
+    pub fn new(c0: i64, c1: i64, c2: i64, c3: i64) -> Self {
+        Self { c: [c0, c1, c2, c3] }
+    }
+
+    /// ζ^k, a rotation by 36k degrees.
+    pub fn zeta_pow(k: i32) -> Self {
+        match k.rem_euclid(10) {
+            0 => Cyclotomic::new( 1,  0,  0,  0),
+            1 => Cyclotomic::new( 0,  1,  0,  0),
+            2 => Cyclotomic::new( 0,  0,  1,  0),
+            3 => Cyclotomic::new( 0,  0,  0,  1),
+            4 => Cyclotomic::new(-1,  1, -1,  1),
+            5 => Cyclotomic::new(-1,  0,  0,  0),
+            6 => Cyclotomic::new( 0, -1,  0,  0),
+            7 => Cyclotomic::new( 0,  0, -1,  0),
+            8 => Cyclotomic::new( 0,  0,  0, -1),
+            _ => Cyclotomic::new( 1, -1,  1, -1),
+        }
+    }
+
+    /// The golden ratio, ζ + ζ⁻¹.
+    pub fn phi() -> Self {
+        Cyclotomic::new(1, 0, 1, -1)
+    }
+
+    /// 1/phi, which is phi - 1 since phi is a unit.
+    pub fn inv_phi() -> Self {
+        Cyclotomic::phi() - Cyclotomic::ONE
+    }
+
+    /// The complex conjugate, a mirror in the real axis: ζ^k becomes ζ^-k.
+    pub fn conj(&self) -> Self {
+        (0..4).fold(Cyclotomic::ZERO, |z, k| z + Cyclotomic::zeta_pow(-(k as i32)).scale_int(self.c[k]))
+    }
+
+    pub fn to_f64(&self) -> (f64, f64) {
+        let mut x = 0.;
+        let mut y = 0.;
+        for k in 0..4 {
+            let a = (36 * k) as f64 * std::f64::consts::PI / 180.;
+            x += self.c[k] as f64 * a.cos();
+            y += self.c[k] as f64 * a.sin();
+        }
+        (x, y)
+    }
+
+    fn is_even(&self) -> bool {
+        self.c.iter().all(|c| c % 2 == 0)
+    }
+
+    fn scale_int(&self, s: i64) -> Self {
+        Cyclotomic::new(self.c[0]*s, self.c[1]*s, self.c[2]*s, self.c[3]*s)
+    }
+}
+
+impl Add for Cyclotomic {
+    type Output = Cyclotomic;
+    fn add(self, o: Cyclotomic) -> Cyclotomic {
+        Cyclotomic::new(self.c[0]+o.c[0], self.c[1]+o.c[1], self.c[2]+o.c[2], self.c[3]+o.c[3])
+    }
+}
+
+impl Sub for Cyclotomic {
+    type Output = Cyclotomic;
+    fn sub(self, o: Cyclotomic) -> Cyclotomic {
+        Cyclotomic::new(self.c[0]-o.c[0], self.c[1]-o.c[1], self.c[2]-o.c[2], self.c[3]-o.c[3])
+    }
+}
+
+impl Neg for Cyclotomic {
+    type Output = Cyclotomic;
+    fn neg(self) -> Cyclotomic {
+        self.scale_int(-1)
+    }
+}
+
+impl Mul for Cyclotomic {
+    type Output = Cyclotomic;
+    fn mul(self, o: Cyclotomic) -> Cyclotomic {
+        let mut p = [0_i64; 7];
+        for i in 0..4 {
+            for j in 0..4 {
+                p[i+j] += self.c[i] * o.c[j];
+            }
+        }
+        // ζ⁶ = -ζ, ζ⁵ = -1, ζ⁴ = ζ³ - ζ² + ζ - 1
+        let mut r = [p[0], p[1], p[2], p[3]];
+        r[1] -= p[6];
+        r[0] -= p[5];
+        r[0] -= p[4];
+        r[1] += p[4];
+        r[2] -= p[4];
+        r[3] += p[4];
+        Cyclotomic { c: r }
+    }
+}
+
+/// A point `num / 2^shift` with `num` in Z[ζ].
+///
+/// Tile centres and edge midpoints need halves and quarters of lattice
+/// points, so the denominator is a power of two. The representation is kept
+/// reduced, which makes `==` and `Hash` exact.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub struct ExactPoint {
+    num: Cyclotomic,
+    shift: u32,
+}
+
+impl ExactPoint {
+    pub fn new(z: Cyclotomic) -> Self {
+        Self { num: z, shift: 0 }
+    }
+
+    pub fn origin() -> Self {
+        ExactPoint::new(Cyclotomic::ZERO)
+    }
+
+    /// `z / 2^shift`.
+    pub fn with_shift(z: Cyclotomic, shift: u32) -> Self {
+        ExactPoint { num: z, shift }.reduced()
+    }
+
+    pub fn numerator(&self) -> Cyclotomic {
+        self.num
+    }
+
+    pub fn shift(&self) -> u32 {
+        self.shift
+    }
+
+    pub fn to_f64(&self) -> (f64, f64) {
+        let (x, y) = self.num.to_f64();
+        let d = (1_u64 << self.shift) as f64;
+        (x / d, y / d)
+    }
+
+    /// Rotate about the origin; only multiples of 36 degrees are exact.
+    pub fn rotate(&self, angle: i32) -> Option<ExactPoint> {
+        if angle % 36 != 0 {
+            return None
+        }
+        Some(self.scale(Cyclotomic::zeta_pow(angle / 36)))
+    }
+
+    pub fn scale(&self, s: Cyclotomic) -> ExactPoint {
+        ExactPoint::with_shift(self.num * s, self.shift)
+    }
+
+    /// Mirror in the x axis.
+    pub fn conj(&self) -> ExactPoint {
+        ExactPoint { num: self.num.conj(), shift: self.shift }
+    }
+
+    pub fn half(&self) -> ExactPoint {
+        ExactPoint::with_shift(self.num, self.shift + 1)
+    }
+
+    pub fn midpoint(&self, o: &ExactPoint) -> ExactPoint {
+        (*self + *o).half()
+    }
+
+    fn reduced(mut self) -> Self {
+        while self.shift > 0 && self.num.is_even() {
+            self.num = Cyclotomic::new(self.num.c[0]/2, self.num.c[1]/2, self.num.c[2]/2, self.num.c[3]/2);
+            self.shift -= 1;
+        }
+        self
+    }
+
+    fn common(&self, o: &ExactPoint) -> (Cyclotomic, Cyclotomic, u32) {
+        let shift = self.shift.max(o.shift);
+        (self.num.scale_int(1 << (shift - self.shift)),
+         o.num.scale_int(1 << (shift - o.shift)),
+         shift)
+    }
+}
+
+impl Add for ExactPoint {
+    type Output = ExactPoint;
+    fn add(self, o: ExactPoint) -> ExactPoint {
+        let (a, b, shift) = self.common(&o);
+        ExactPoint::with_shift(a + b, shift)
+    }
+}
+
+impl Sub for ExactPoint {
+    type Output = ExactPoint;
+    fn sub(self, o: ExactPoint) -> ExactPoint {
+        let (a, b, shift) = self.common(&o);
+        ExactPoint::with_shift(a - b, shift)
+    }
+}
+
+impl Neg for ExactPoint {
+    type Output = ExactPoint;
+    fn neg(self) -> ExactPoint {
+        ExactPoint { num: -self.num, shift: self.shift }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dist_func(p1: (f64,f64), p2: (f64,f64)) -> f64 {
+        let dx = p2.0 - p1.0;
+        let dy = p2.1 - p1.1;
+        (dx*dx + dy*dy).sqrt()
+    }
+
+    #[test]
+    fn test_zeta_powers() {
+        for k in 0..10 {
+            let a = (36 * k) as f64 * std::f64::consts::PI / 180.;
+            assert!(dist_func(Cyclotomic::zeta_pow(k).to_f64(), (a.cos(), a.sin())) < 1e-12);
+            assert_eq!(Cyclotomic::zeta_pow(k) * Cyclotomic::zeta_pow(1), Cyclotomic::zeta_pow(k + 1));
+        }
+        assert_eq!(Cyclotomic::zeta_pow(-3), Cyclotomic::zeta_pow(7));
+    }
+
+    #[test]
+    fn test_conj() {
+        for k in 0..10 {
+            assert_eq!(Cyclotomic::zeta_pow(k).conj(), Cyclotomic::zeta_pow(-k));
+        }
+        let z = Cyclotomic::new(3, -1, 4, 2);
+        let (x, y) = z.to_f64();
+        assert!(dist_func(z.conj().to_f64(), (x, -y)) < 1e-12);
+        assert_eq!(z.conj().conj(), z);
+        assert_eq!((z * Cyclotomic::phi()).conj(), z.conj() * Cyclotomic::phi());
+
+        let p = ExactPoint::new(z).half();
+        assert!(dist_func(p.conj().to_f64(), (x / 2., -y / 2.)) < 1e-12);
+    }
+
+    #[test]
+    fn test_phi() {
+        let phi = (1. + 5_f64.sqrt()) / 2.;
+        assert!(dist_func(Cyclotomic::phi().to_f64(), (phi, 0.)) < 1e-12);
+        assert_eq!(Cyclotomic::phi() * Cyclotomic::phi(), Cyclotomic::phi() + Cyclotomic::ONE);
+        assert_eq!(Cyclotomic::phi() * Cyclotomic::inv_phi(), Cyclotomic::ONE);
+    }
+
+    #[test]
+    fn test_point_reduction() {
+        let z = Cyclotomic::new(1, 2, 3, 4);
+        let p = ExactPoint::new(z);
+        assert_eq!(p.half() + p.half(), p);
+        assert_eq!((p.half() + p.half()).shift(), 0);
+        assert_eq!(p.half().half() - p.half(), -p.half().half());
+        assert_eq!(ExactPoint::with_shift(z.scale_int(4), 2), p);
+    }
+
+    #[test]
+    fn test_point_rotate() {
+        let p = ExactPoint::new(Cyclotomic::phi()).half();
+        assert_eq!(p.rotate(90), None);
+
+        let mut q = p;
+        for _ in 0..10 {
+            q = q.rotate(36).unwrap();
+        }
+        assert_eq!(q, p);
+
+        let (x, y) = p.rotate(72).unwrap().to_f64();
+        let (x0, y0) = p.to_f64();
+        let a = 72_f64 * std::f64::consts::PI / 180.;
+        assert!(dist_func((x, y), (x0*a.cos() - y0*a.sin(), x0*a.sin() + y0*a.cos())) < 1e-12);
+    }
+}
@@ -0,0 +1,463 @@
+//! Saving and loading patches.
+//!
+//! A patch is stored as JSON:
+//!
+//! ```json
+//! {
+//!   "format": "penrose-patch",
+//!   "version": 1,
+//!   "view": { "scale": 25.0, "rotation": 0.0, "show_edges": true, "show_arcs": true, "show_bars": false },
+//!   "tiles": [
+//!     { "kind": "KITE", "cx": 0.0, "cy": 0.0, "angle": 0 },
+//!     { "kind": "DART", "cx": 0.5, "cy": -1.5388417685876268, "angle": 324 },
+//!     { "kind": "SKINNY", "cx": 3.0, "cy": 1.0, "angle": 72, "reflected": true }
+//!   ]
+//! }
+//! ```
+//!
+//! `kind` is one of the `Tile` names, `cx` and `cy` are the tile centre and
+//! `angle` is in degrees and a multiple of 36. `reflected` marks a mirrored
+//! kite, dart or rhombus, and is only written for those. `view` may be left
+//! out, as may any of its fields, which then take their usual values. Other
+//! fields are ignored, so later versions can add to the format.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::tiles::*;
+
+pub const PATCH_FORMAT: &str = "penrose-patch";
+pub const PATCH_VERSION: i64 = 1;
+
+/// How the patch was being looked at.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ViewSettings {
+    pub scale: f64,
+    /// How far the whole patch is turned, in degrees.
+    pub rotation: f64,
+    pub show_edges: bool,
+    pub show_arcs: bool,
+    pub show_bars: bool,
+}
+
+impl Default for ViewSettings {
+    fn default() -> Self {
+        ViewSettings { scale: 25., rotation: 0., show_edges: true, show_arcs: true, show_bars: false }
+    }
+}
+
+pub struct PatchFile {
+    pub tiles: Vec<Box<dyn PenroseTile>>,
+    pub view: ViewSettings,
+}
+
+#[derive(Debug)]
+pub enum FileError {
+    Io(std::io::Error),
+    /// The text is not JSON; `offset` is the byte where reading stopped.
+    Syntax { offset: usize, message: String },
+    /// The JSON is not a patch.
+    Invalid(String),
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileError::Io(e) => write!(f, "{}", e),
+            FileError::Syntax { offset, message } => write!(f, "bad JSON at byte {}: {}", offset, message),
+            FileError::Invalid(message) => write!(f, "not a patch: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+impl From<std::io::Error> for FileError {
+    fn from(e: std::io::Error) -> Self {
+        FileError::Io(e)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, FileError> {
+        Err(FileError::Syntax { offset: self.pos, message: message.to_string() })
+    }
+
+    fn skip_space(&mut self) {
+        while self.pos < self.text.len() && b" \t\r\n".contains(&self.text[self.pos]) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), FileError> {
+        self.skip_space();
+        if self.text.get(self.pos) != Some(&c) {
+            return self.error(&format!("expected '{}'", c as char))
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, FileError> {
+        if self.text[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            self.error("unexpected character")
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, FileError> {
+        self.skip_space();
+        match self.text.get(self.pos) {
+            None => self.error("unexpected end"),
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Json::String(self.string()?)),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(_) => self.number(),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, FileError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_space();
+        if self.text.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields))
+        }
+        loop {
+            self.skip_space();
+            if self.text.get(self.pos) != Some(&b'"') {
+                return self.error("expected a field name")
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            self.skip_space();
+            match self.text.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields))
+                }
+                _ => return self.error("expected ',' or '}'"),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, FileError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_space();
+        if self.text.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items))
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_space();
+            match self.text.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items))
+                }
+                _ => return self.error("expected ',' or ']'"),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, FileError> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.text.get(self.pos) {
+                None => return self.error("unterminated string"),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.text.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let hex = self.text.get(self.pos+1..self.pos+5)
+                                .and_then(|h| std::str::from_utf8(h).ok())
+                                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                                .and_then(std::char::from_u32);
+                            match hex {
+                                Some(c) => {
+                                    self.pos += 4;
+                                    c
+                                }
+                                None => return self.error("bad \\u escape"),
+                            }
+                        }
+                        _ => return self.error("bad escape"),
+                    };
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                Some(b) => bytes.push(*b),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        match String::from_utf8(bytes) {
+            Ok(s) => Ok(s),
+            Err(_) => self.error("string is not UTF-8"),
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, FileError> {
+        let start = self.pos;
+        while self.pos < self.text.len() && b"+-0123456789.eE".contains(&self.text[self.pos]) {
+            self.pos += 1;
+        }
+        let parsed = std::str::from_utf8(&self.text[start..self.pos]).ok()
+            .filter(|s| !s.starts_with('+') && !s.starts_with('.'))
+            .and_then(|s| s.parse::<f64>().ok());
+        match parsed {
+            Some(x) => Ok(Json::Number(x)),
+            None => {
+                self.pos = start;
+                self.error("expected a value")
+            }
+        }
+    }
+}
+
+fn parse(text: &str) -> Result<Json, FileError> {
+    let mut parser = Parser { text: text.as_bytes(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_space();
+    if parser.pos < parser.text.len() {
+        return parser.error("text after the patch")
+    }
+    Ok(value)
+}
+
+fn invalid<T>(message: String) -> Result<T, FileError> {
+    Err(FileError::Invalid(message))
+}
+
+fn number(json: &Json, what: &str) -> Result<f64, FileError> {
+    match json {
+        Json::Number(x) if x.is_finite() => Ok(*x),
+        _ => invalid(format!("{} is not a number", what)),
+    }
+}
+
+fn read_tile(json: &Json, i: usize) -> Result<Box<dyn PenroseTile>, FileError> {
+    let field = |key: &str| json.get(key).ok_or_else(|| FileError::Invalid(format!("tile {} has no {}", i, key)));
+    let kind = match field("kind")? {
+        Json::String(name) => match Tile::from_name(name) {
+            Some(kind) => kind,
+            None => return invalid(format!("tile {} is of unknown kind {}", i, name)),
+        },
+        _ => return invalid(format!("tile {} kind is not a string", i)),
+    };
+    let cx = number(field("cx")?, &format!("tile {} cx", i))?;
+    let cy = number(field("cy")?, &format!("tile {} cy", i))?;
+    let angle = number(field("angle")?, &format!("tile {} angle", i))?;
+    if angle.fract() != 0. || angle as i64 % 36 != 0 || angle.abs() > 1e9 {
+        return invalid(format!("tile {} angle {} is not a multiple of 36", i, angle))
+    }
+    let tile = new_tile(kind, cx, cy, (angle as i64).rem_euclid(360) as i32);
+    match json.get("reflected") {
+        None | Some(Json::Bool(false)) => Ok(tile),
+        Some(Json::Bool(true)) if !kind.is_triangle() => Ok(reflect_tile(tile.as_ref())),
+        Some(Json::Bool(true)) => invalid(format!("tile {} is a triangle, which has a mirrored kind instead", i)),
+        Some(_) => invalid(format!("tile {} reflected is not true or false", i)),
+    }
+}
+
+fn read_view(json: Option<&Json>) -> Result<ViewSettings, FileError> {
+    let mut view = ViewSettings::default();
+    let json = match json {
+        None => return Ok(view),
+        Some(json @ Json::Object(_)) => json,
+        Some(_) => return invalid("view is not an object".to_string()),
+    };
+    if let Some(scale) = json.get("scale") {
+        view.scale = number(scale, "view scale")?;
+        if view.scale <= 0. {
+            return invalid("view scale is not positive".to_string())
+        }
+    }
+    if let Some(rotation) = json.get("rotation") {
+        view.rotation = number(rotation, "view rotation")?;
+    }
+    for (key, flag) in [("show_edges", &mut view.show_edges), ("show_arcs", &mut view.show_arcs),
+                        ("show_bars", &mut view.show_bars)].iter_mut() {
+        match json.get(key) {
+            None => {}
+            Some(Json::Bool(b)) => **flag = *b,
+            Some(_) => return invalid(format!("view {} is not true or false", key)),
+        }
+    }
+    Ok(view)
+}
+
+/// Read a patch from the text of a patch file, checking that it is one.
+pub fn read_patch(text: &str) -> Result<PatchFile, FileError> {
+    let json = parse(text)?;
+    if json.get("format") != Some(&Json::String(PATCH_FORMAT.to_string())) {
+        return invalid(format!("format is not \"{}\"", PATCH_FORMAT))
+    }
+    match json.get("version") {
+        Some(Json::Number(v)) if *v == PATCH_VERSION as f64 => {}
+        Some(Json::Number(v)) => return invalid(format!("version {} is not supported", v)),
+        _ => return invalid("there is no version".to_string()),
+    }
+    let tiles = match json.get("tiles") {
+        Some(Json::Array(items)) => items.iter().enumerate()
+            .map(|(i, t)| read_tile(t, i))
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => return invalid("there is no list of tiles".to_string()),
+    };
+    Ok(PatchFile { tiles, view: read_view(json.get("view"))? })
+}
+
+/// The text of a patch file for `tiles`.
+pub fn write_patch(tiles: &[Box<dyn PenroseTile>], view: &ViewSettings) -> String {
+    let mut result = String::new();
+    result += "{\n";
+    result += &format!("  \"format\": \"{}\",\n", PATCH_FORMAT);
+    result += &format!("  \"version\": {},\n", PATCH_VERSION);
+    result += &format!("  \"view\": {{ \"scale\": {:?}, \"rotation\": {:?}, \"show_edges\": {}, \"show_arcs\": {}, \"show_bars\": {} }},\n",
+                       view.scale, view.rotation, view.show_edges, view.show_arcs, view.show_bars);
+    result += "  \"tiles\": [";
+    for (i, t) in tiles.iter().enumerate() {
+        result += if i == 0 { "\n" } else { ",\n" };
+        result += &format!("    {{ \"kind\": \"{}\", \"cx\": {:?}, \"cy\": {:?}, \"angle\": {}{} }}",
+                           t.kind().name(), t.center().0, t.center().1, t.angle(),
+                           if t.reflected() { ", \"reflected\": true" } else { "" });
+    }
+    result += if tiles.is_empty() { "]\n" } else { "\n  ]\n" };
+    result += "}\n";
+    result
+}
+
+pub fn save_patch<P: AsRef<Path>>(path: P, tiles: &[Box<dyn PenroseTile>], view: &ViewSettings) -> Result<(), FileError> {
+    std::fs::write(path, write_patch(tiles, view))?;
+    Ok(())
+}
+
+pub fn load_patch<P: AsRef<Path>>(path: P) -> Result<PatchFile, FileError> {
+    read_patch(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::*;
+    use crate::deflate::tests::*;
+    use crate::deflate::*;
+
+    #[test]
+    fn test_round_trip() {
+        let view = ViewSettings { scale: 12.5, rotation: -17.25, show_edges: false, show_arcs: true, show_bars: true };
+        let mirrored: Vec<Box<dyn PenroseTile>> = deflate_n(&star(), 2).iter().map(|t| reflect_tile(t.as_ref())).collect();
+        for tiles in [deflate_n(&sun(), 3), to_triangles(&deflate_n(&star(), 2)), mirrored, Vec::new()].iter() {
+            let file = read_patch(&write_patch(tiles, &view)).unwrap();
+            assert_eq!(file.view, view);
+            assert_eq!(file.tiles.len(), tiles.len());
+            for (a, b) in tiles.iter().zip(file.tiles.iter()) {
+                assert_eq!(a.kind(), b.kind());
+                assert_eq!(a.reflected(), b.reflected());
+                assert_eq!(a.angle(), b.angle());
+                assert_eq!(a.center(), b.center());
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_patch() {
+        // Fields in any order, extra fields, no view, and spacing and escapes
+        // as JSON allows.
+        let text = r#" { "tiles" : [ {"angle": -36, "cy": -1.5e0, "kind": "DART", "cx": 2, "note": [null, {}]},
+                                     {"kind": "SKINNY", "cx": 0, "cy": 0, "angle": 0, "reflected": true} ],
+                         "version": 1, "format": "penrose-patch" } "#;
+        let file = read_patch(text).unwrap();
+        assert_eq!(file.view, ViewSettings::default());
+        assert_eq!(file.tiles[0].kind(), Tile::DART);
+        assert_eq!(file.tiles[0].center(), (2., -1.5));
+        assert_eq!(file.tiles[0].angle(), 324);
+        assert!(!file.tiles[0].reflected() && file.tiles[1].reflected());
+    }
+
+    #[test]
+    fn test_bad_patches() {
+        let patch = |tiles: &str, view: &str| format!(
+            r#"{{"format": "penrose-patch", "version": 1, "view": {}, "tiles": [{}]}}"#, view, tiles);
+        let tile = r#"{"kind": "KITE", "cx": 0, "cy": 0, "angle": 0}"#;
+        assert!(read_patch(&patch(tile, "{}")).is_ok());
+
+        let syntax = ["", "{", r#"{"format": }"#, r#"{"a": [1, 2,]}"#, r#"{"a": "\q"}"#, "{} {}", "[.5]"];
+        for text in syntax.iter() {
+            match read_patch(text) {
+                Err(FileError::Syntax { .. }) => {}
+                _ => panic!("{} should not parse", text),
+            }
+        }
+        match read_patch(r#"{"a": tru}"#) {
+            Err(FileError::Syntax { offset, .. }) => assert_eq!(offset, 6),
+            _ => panic!(),
+        }
+
+        let invalid = [patch(r#"{"kind": "SQUARE", "cx": 0, "cy": 0, "angle": 0}"#, "{}"),
+                       patch(r#"{"kind": "KITE", "cx": 0, "cy": 0, "angle": 30}"#, "{}"),
+                       patch(r#"{"kind": "KITE", "cx": 0, "cy": 0, "angle": 36.5}"#, "{}"),
+                       patch(r#"{"kind": "KITE", "cx": "0", "cy": 0, "angle": 0}"#, "{}"),
+                       patch(r#"{"kind": "KITE", "cy": 0, "angle": 0}"#, "{}"),
+                       patch(r#"{"kind": "KITE", "cx": 0, "cy": 0, "angle": 0, "reflected": 1}"#, "{}"),
+                       patch(r#"{"kind": "ROBINSON_A", "cx": 0, "cy": 0, "angle": 0, "reflected": true}"#, "{}"),
+                       patch(tile, r#"{"scale": -1}"#),
+                       patch(tile, r#"{"rotation": "left"}"#),
+                       patch(tile, r#"{"show_arcs": 1}"#),
+                       patch(tile, "[]"),
+                       patch(tile, "{}").replace("\"version\": 1", "\"version\": 2"),
+                       patch(tile, "{}").replace("penrose-patch", "something-else"),
+                       r#"{"format": "penrose-patch", "version": 1}"#.to_string()];
+        for text in invalid.iter() {
+            match read_patch(text) {
+                Err(FileError::Invalid(_)) => {}
+                _ => panic!("{} should be invalid", text),
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Inflation (composition) of patches.
+//!
+//! The inverse of `deflate`: tiles are grouped into the parent tiles whose
+//! substitution produces them. A parent is only accepted if every one of its
+//! children is in the patch, and in a legal patch each such group has a
+//! unique parent. Tiles near the border of the patch may belong to a parent
+//! that is only partly present; these are reported as uncomposed.
+//!
+//! As with `deflate`, the parents are returned at the original tile size, so
+//! the patch shrinks by a factor of phi and `inflate(&deflate(x)).tiles`
+//! gives back `x`.
+
+use crate::convert::*;
+use crate::deflate::*;
+use crate::exact::Cyclotomic;
+use crate::tiles::*;
+
+/// The result of composing a patch.
+pub struct Inflation {
+    /// The parent tiles, in the order they were found.
+    pub tiles: Vec<Box<dyn PenroseTile>>,
+    /// Indices of input tiles not wholly covered by the parents: tiles with
+    /// no parent, or with only one of their half-tiles inside a parent.
+    pub uncomposed: Vec<usize>,
+}
+
+// The parents that `t` could be a child of, placed in inflated coordinates.
+// A reflected tile can only be the child of a reflected parent, which holds
+// the mirror images of the children in `children`.
+fn candidate_parents(t: &dyn PenroseTile) -> Vec<Box<dyn PenroseTile>> {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+
+    let kinds: &[Tile] = match t.kind() {
+        Tile::KITE | Tile::DART => &[Tile::KITE, Tile::DART],
+        Tile::FAT | Tile::SKINNY => &[Tile::FAT, Tile::SKINNY],
+        _ => &[],
+    };
+    let mut result = Vec::new();
+    for kind in kinds.iter() {
+        for c in children(*kind).iter().filter(|c| c.kind == t.kind()) {
+            let (offset, angle) = if t.reflected() {
+                (c.center.conj(), (t.angle() + c.angle).rem_euclid(360))
+            } else {
+                (c.center, (t.angle() - c.angle).rem_euclid(360))
+            };
+            let parent = match offset_center(t.center(), t.exact_center(), -offset, angle) {
+                (_, Some(p)) => new_exact_tile(*kind, p.scale(Cyclotomic::inv_phi()), angle),
+                ((x, y), None) => new_tile(*kind, x/phi, y/phi, angle),
+            };
+            result.push(if t.reflected() { reflect_tile(parent.as_ref()) } else { parent });
+        }
+    }
+    result
+}
+
+/// Group the tiles of a kite and dart or rhombus patch into their parent
+/// tiles.
+///
+/// Robinson triangles on their own can be grouped in more than one way (two
+/// of the pieces of a half-kite also make a half-dart), so a patch of
+/// triangles is composed as the kites and darts they form. A parent triangle
+/// is returned if its kite or dart is composed and all of its own pieces are
+/// in the patch.
+pub fn inflate(tiles: &[Box<dyn PenroseTile>]) -> Inflation {
+    if tiles.iter().any(|t| t.kind().is_triangle()) {
+        return inflate_triangles(tiles)
+    }
+
+    let mut lookup = TileLookup::new();
+    for (i, t) in tiles.iter().enumerate() {
+        lookup.insert(t.as_ref(), i);
+    }
+
+    let mut covered = vec![0_u8; tiles.len()];
+    let mut parents = TileLookup::new();
+    let mut result = Vec::new();
+    for t in tiles {
+        for parent in candidate_parents(t.as_ref()) {
+            if parents.get(parent.as_ref()).is_some() {
+                continue
+            }
+            let found: Option<Vec<(usize, u8)>> = substitute(parent.as_ref()).iter()
+                .map(|(child, halves)| lookup.get(child.as_ref()).map(|i| (i, *halves)))
+                .collect();
+            if let Some(found) = found {
+                for (i, halves) in found {
+                    covered[i] += halves;
+                }
+                parents.insert(parent.as_ref(), result.len());
+                result.push(parent);
+            }
+        }
+    }
+
+    Inflation {
+        tiles: result,
+        uncomposed: (0..tiles.len()).filter(|i| covered[*i] < 2).collect(),
+    }
+}
+
+fn inflate_triangles(tiles: &[Box<dyn PenroseTile>]) -> Inflation {
+    let mut present = TileLookup::new();
+    for (i, t) in tiles.iter().enumerate() {
+        present.insert(t.as_ref(), i);
+    }
+
+    let inflation = inflate(&from_triangles(tiles));
+    let mut covered = vec![false; tiles.len()];
+    let mut result = Vec::new();
+    for parent in to_triangles(&inflation.tiles) {
+        let found: Option<Vec<usize>> = substitute(parent.as_ref()).iter()
+            .map(|(child, _)| present.get(child.as_ref()))
+            .collect();
+        if let Some(found) = found {
+            for i in found {
+                covered[i] = true;
+            }
+            result.push(parent);
+        }
+    }
+
+    Inflation {
+        tiles: result,
+        uncomposed: (0..tiles.len()).filter(|i| !covered[*i]).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exact::ExactPoint;
+    use std::collections::HashSet;
+
+    fn exact_key(t: &dyn PenroseTile) -> (Tile, bool, i32, Option<ExactPoint>) {
+        (t.kind(), t.reflected(), t.angle(), t.exact_center())
+    }
+
+    fn seeds() -> Vec<Vec<Box<dyn PenroseTile>>> {
+        let sun: Vec<Box<dyn PenroseTile>> = (0..5).map(|i| -> Box<dyn PenroseTile> {
+            Box::new(Kite::new_exact(ExactPoint::new(Cyclotomic::phi() * Cyclotomic::zeta_pow(2*i)), 72*i))
+        }).collect();
+        let star = (0..5).map(|i| -> Box<dyn PenroseTile> {
+            Box::new(Dart::new_exact(ExactPoint::new(-Cyclotomic::phi() * Cyclotomic::zeta_pow(2*i)), 72*i))
+        }).collect();
+        vec![sun, star,
+             vec![new_exact_tile(Tile::FAT, ExactPoint::origin(), 0)],
+             vec![new_exact_tile(Tile::SKINNY, ExactPoint::origin(), 0)]]
+    }
+
+    fn keys(tiles: &[Box<dyn PenroseTile>]) -> HashSet<(Tile, bool, i32, Option<ExactPoint>)> {
+        tiles.iter().map(|t| exact_key(t.as_ref())).collect()
+    }
+
+    #[test]
+    fn test_inflate_deflated() {
+        for seed in seeds() {
+            let mut tiles = seed;
+            for _ in 0..3 {
+                let deflated = deflate(&tiles);
+                let inflated = inflate(&deflated);
+                assert_eq!(inflated.tiles.len(), tiles.len());
+                assert_eq!(keys(&inflated.tiles), keys(&tiles));
+                tiles = deflated;
+            }
+        }
+    }
+
+    #[test]
+    fn test_inflate_reflected() {
+        // Mirrored patches compose into mirrored parents.
+        for seed in seeds() {
+            let mut tiles: Vec<Box<dyn PenroseTile>> = seed.iter().map(|t| reflect_tile(t.as_ref())).collect();
+            for _ in 0..2 {
+                let deflated = deflate(&tiles);
+                let inflated = inflate(&deflated);
+                assert_eq!(inflated.tiles.len(), tiles.len());
+                assert!(inflated.tiles.iter().all(|t| t.reflected()));
+                assert_eq!(keys(&inflated.tiles), keys(&tiles));
+                tiles = deflated;
+            }
+        }
+    }
+
+    #[test]
+    fn test_inflate_triangles() {
+        for seed in seeds().iter().take(2) {
+            for n in 0..3 {
+                let tiles = to_triangles(&deflate_n(seed, n));
+                let inflated = inflate(&deflate(&tiles));
+                assert_eq!(inflated.tiles.len(), tiles.len());
+                assert_eq!(keys(&inflated.tiles), keys(&tiles));
+            }
+        }
+
+        // Without the rest of its kite, half a kite's pieces are not composed.
+        let tiles = deflate(&[new_exact_tile(Tile::ROBINSON_A, ExactPoint::origin(), 0)]);
+        let inflated = inflate(&tiles);
+        assert!(inflated.tiles.is_empty());
+        assert_eq!(inflated.uncomposed.len(), tiles.len());
+    }
+
+    #[test]
+    fn test_inflate_float() {
+        let tiles: Vec<Box<dyn PenroseTile>> = deflate_n(&seeds()[0], 2).iter()
+            .map(|t| new_tile(t.kind(), t.center().0, t.center().1, t.angle()))
+            .collect();
+        let inflated = inflate(&deflate(&tiles));
+        assert_eq!(inflated.tiles.len(), tiles.len());
+        for t in tiles.iter() {
+            assert!(inflated.tiles.iter().any(|p| p.kind() == t.kind() && p.angle() == t.angle()
+                && (p.center().0 - t.center().0).abs() < 1e-9
+                && (p.center().1 - t.center().1).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_inflate_uncomposed() {
+        // A deflated sun with one child removed: that kite's parent can no
+        // longer be composed, so its remaining children are reported.
+        let mut tiles = deflate(&seeds()[0]);
+        let removed = tiles.remove(0);
+        let inflated = inflate(&tiles);
+        assert_eq!(inflated.tiles.len(), 4);
+        assert!(!inflated.uncomposed.is_empty());
+
+        let parent = candidate_parents(removed.as_ref()).into_iter()
+            .find(|p| keys(&seeds()[0]).contains(&exact_key(p.as_ref())))
+            .unwrap();
+        for (child, halves) in substitute(parent.as_ref()) {
+            if let Some(i) = tiles.iter().position(|t| exact_key(t.as_ref()) == exact_key(child.as_ref())) {
+                assert!(halves == 1 || inflated.uncomposed.contains(&i));
+            }
+        }
+
+        // A single tile has no parent.
+        let inflated = inflate(&seeds()[2]);
+        assert!(inflated.tiles.is_empty());
+        assert_eq!(inflated.uncomposed, vec![0]);
+    }
+}
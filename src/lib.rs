@@ -0,0 +1,39 @@
+//! Penrose tile geometry.
+//!
+//! This crate has no GUI dependencies; the interactive nannou app in
+//! `main.rs` is built on top of it behind the `gui` feature.
+
+mod convert;
+mod deflate;
+pub mod exact;
+mod file;
+mod inflate;
+mod mesh;
+mod patch;
+mod pentagrid;
+mod projection;
+mod raster;
+mod rules;
+mod spatial;
+mod style;
+mod svg;
+mod tiles;
+mod transform;
+mod vertex;
+
+pub use convert::*;
+pub use deflate::*;
+pub use file::*;
+pub use inflate::*;
+pub use mesh::*;
+pub use patch::*;
+pub use pentagrid::*;
+pub use projection::*;
+pub use raster::*;
+pub use rules::*;
+pub use spatial::*;
+pub use style::*;
+pub use svg::*;
+pub use tiles::*;
+pub use transform::*;
+pub use vertex::*;
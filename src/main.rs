@@ -1,10 +1,5 @@
 use nannou::prelude::*;
 
-#[macro_use]
-
-#[path = "penrose.rs"]
-mod penrose;
-
 use penrose::*;
 
 struct DrawProps {
@@ -19,18 +14,9 @@ struct DrawProps {
     show_bars: bool,
 }
 
-trait Drawable {
+trait Drawable: PenroseTile {
     fn draw(&self, draw: &nannou::draw::Draw, xoff: f32, yoff: f32, scale: f32, props: &DrawProps);
     fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64);
-    fn get_drawable_edges(&self) -> Vec<Edge>;
-    fn get_ammann_bars(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)>;
-}
-
-fn interpolate(p1: (f32,f32), p2: (f32,f32), t: f64) -> (f32,f32) {
-    return (
-        (p1.0 as f64 + t * ( p2.0 as f64 - p1.0 as f64)) as f32,
-        (p1.1 as f64 + t * ( p2.1 as f64 - p1.1 as f64)) as f32
-    )
 }
 
 impl Drawable for Dart {
@@ -74,48 +60,18 @@ impl Drawable for Dart {
         }
 
         if (props.show_bars) {
-            let a = self.get_ammann_bars(xoff, yoff, scale);
+            let a = self.get_ammann_bars();
             for i in (0..a.len()).step_by(2) {
                 draw.line()
-                    .start(pt2(a[i].0, a[i].1))
-                    .end(pt2(a[i+1].0, a[i+1].1))
+                    .start(pt2(xoff + scale*a[i].0 as f32, yoff + scale*a[i].1 as f32))
+                    .end(pt2(xoff + scale*a[i+1].0 as f32, yoff + scale*a[i+1].1 as f32))
                     .color(props.bar_color);
             }
         }
     }
 
     fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
-        dst.push(Box::new(penrose::Dart {
-            cx: self.cx + dx,
-            cy: self.cy + dy,
-            angle: self.angle
-        }));
-    }
-
-    fn get_drawable_edges(&self) -> Vec<Edge> {
-        self.get_edges()
-    }
-
-    fn get_ammann_bars(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)> {
-        let s5 = 5_f64.sqrt();
-        let t1 = 1. / 4.;
-        let t2 = 1. / (3. + s5);
-        let t3 = (1. + s5) / 4.;
-
-        let p = self.polygon(xoff, yoff, scale);
-        let a0 = interpolate(p[1], p[0], t3);
-        let a1 = interpolate(p[1], p[0], t1);
-        let a2 = interpolate(p[1], p[2], t2);
-        let a3 = interpolate(p[3], p[2], t2);
-        let a4 = interpolate(p[3], p[0], t1);
-        let a5 = interpolate(p[3], p[0], t3);
-
-        let mut result = Vec::new();
-        result.push(a1); result.push(a2);
-        result.push(a2); result.push(a0);
-        result.push(a5); result.push(a3);
-        result.push(a3); result.push(a4);
-        return result
+        dst.push(Box::new(self.translate(dx, dy)));
     }
 }
 
@@ -163,45 +119,18 @@ impl Drawable for Kite {
         }
 
         if (props.show_bars) {
-            let a = self.get_ammann_bars(xoff, yoff, scale);
+            let a = self.get_ammann_bars();
             for i in (0..a.len()).step_by(2) {
                 draw.line()
-                    .start(pt2(a[i].0, a[i].1))
-                    .end(pt2(a[i+1].0, a[i+1].1))
+                    .start(pt2(xoff + scale*a[i].0 as f32, yoff + scale*a[i].1 as f32))
+                    .end(pt2(xoff + scale*a[i+1].0 as f32, yoff + scale*a[i+1].1 as f32))
                     .color(props.bar_color);
             }
         }
     }
 
     fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
-        dst.push(Box::new(penrose::Kite {
-            cx: self.cx + dx,
-            cy: self.cy + dy,
-            angle: self.angle
-        }));
-    }
-
-    fn get_drawable_edges(&self) -> Vec<Edge> {
-        self.get_edges()
-    }
-
-    fn get_ammann_bars(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)> {
-        let s5 = 5_f64.sqrt();
-        let t1 = 1. / 4.;
-        let t2 = 1. / (3. + s5);
-
-        let p = self.polygon(xoff, yoff, scale);
-        let r0 = interpolate(p[0], p[1], t2);
-        let r3 = interpolate(p[2], p[1], t1);
-        let r4 = interpolate(p[2], p[3], t1);
-        let r7 = interpolate(p[0], p[3], t2);
-
-        let mut result = Vec::new();
-        result.push(r3); result.push(r7);
-        result.push(r7); result.push(r0);
-        result.push(r0); result.push(r4);
-
-        return result
+        dst.push(Box::new(self.translate(dx, dy)));
     }
 }
 
@@ -247,46 +176,18 @@ impl Drawable for Skinny {
         }
 
         if (props.show_bars) {
-            let a = self.get_ammann_bars(xoff, yoff, scale);
+            let a = self.get_ammann_bars();
             for i in (0..a.len()).step_by(2) {
                 draw.line()
-                    .start(pt2(a[i].0, a[i].1))
-                    .end(pt2(a[i+1].0, a[i+1].1))
+                    .start(pt2(xoff + scale*a[i].0 as f32, yoff + scale*a[i].1 as f32))
+                    .end(pt2(xoff + scale*a[i+1].0 as f32, yoff + scale*a[i+1].1 as f32))
                     .color(props.bar_color);
             }
         }
     }
 
     fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
-        dst.push(Box::new(penrose::Skinny {
-            cx: self.cx + dx,
-            cy: self.cy + dy,
-            angle: self.angle
-        }));
-    }
-
-    fn get_drawable_edges(&self) -> Vec<Edge> {
-        self.get_edges()
-    }
-
-    fn get_ammann_bars(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)> {
-        let s5 = 5_f64.sqrt();
-        let t1 = 1. / 4.;
-        let x2 = (s5 - 1.0) / 8.0;
-        let x3 = (5.0 + 3.0 * s5) / 8.0;
-        let t4 = (1. - t1) * (x3 + x2) / (x3 - x2);
-
-        let p = self.polygon(xoff, yoff, scale);
-        let a0 = interpolate(p[3], p[0], t1);
-        let a1 = interpolate(p[0], p[1], t4);
-        let a2 = interpolate(p[2], p[1], t4);
-        let a3 = interpolate(p[3], p[2], t1);
-
-        let mut result = Vec::new();
-        result.push(a0); result.push(a1);
-        result.push(a2); result.push(a3);
-
-        return result
+        dst.push(Box::new(self.translate(dx, dy)));
     }
 }
 
@@ -332,57 +233,143 @@ impl Drawable for Fat {
         }
 
         if (props.show_bars) {
-            let a = self.get_ammann_bars(xoff, yoff, scale);
+            let a = self.get_ammann_bars();
             for i in (0..a.len()).step_by(2) {
                 draw.line()
-                    .start(pt2(a[i].0, a[i].1))
-                    .end(pt2(a[i+1].0, a[i+1].1))
+                    .start(pt2(xoff + scale*a[i].0 as f32, yoff + scale*a[i].1 as f32))
+                    .end(pt2(xoff + scale*a[i+1].0 as f32, yoff + scale*a[i+1].1 as f32))
                     .color(props.bar_color);
             }
         }
     }
 
     fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
-        dst.push(Box::new(penrose::Fat {
-            cx: self.cx + dx,
-            cy: self.cy + dy,
-            angle: self.angle
-        }));
+        dst.push(Box::new(self.translate(dx, dy)));
+    }
+}
+
+impl Drawable for RobinsonA {
+    fn draw(&self, draw: &nannou::draw::Draw, xoff: f32, yoff: f32, scale: f32, props: &DrawProps) {
+        let pts = self.polygon(xoff, yoff, scale);
+        let points = (0..pts.len()).map(|i| {
+            pt2(pts[i].0, pts[i].1)
+        });
+        draw.polygon()
+            .color(props.fill_color2)
+            .stroke(props.edge_color)
+            .stroke_weight(props.edge_weight)
+            .join_miter()
+            .points(points);
+
+        if (props.show_arcs) {
+            let a1 = self.get_small_arc();
+            let angles = interp_angles(a1.start_angle, a1.end_angle);
+            let radius = a1.radius;
+            let arc_points = angles.iter().map(|a| {
+                pt2(xoff + scale*(a1.center.0 + radius * a.cos()) as f32,
+                    yoff + scale*(a1.center.1 + radius * a.sin()) as f32)
+            });
+            draw.polyline()
+                .color(props.arc1_color)
+                .stroke_weight(2.)
+                .points(arc_points);
+
+            //
+            let a2 = self.get_big_arc();
+            let angles = interp_angles(a2.start_angle, a2.end_angle);
+            let radius = a2.radius;
+            let arc_points = angles.iter().map(|a| {
+                pt2(xoff + scale*(a2.center.0 + radius * a.cos()) as f32,
+                    yoff + scale*(a2.center.1 + radius * a.sin()) as f32)
+            });
+            draw.polyline()
+                .color(props.arc2_color)
+                .stroke_weight(2.)
+                .points(arc_points);
+        }
     }
 
-    fn get_drawable_edges(&self) -> Vec<Edge> {
-        self.get_edges()
+    fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
+        dst.push(Box::new(self.translate(dx, dy)));
     }
+}
 
-    fn get_ammann_bars(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)> {
-        let s5 = 5_f64.sqrt();
-        let t1 = 1. / 4.;
-        let t2 = 1. / (3. + s5);
-        let t3 = (1. + s5) / 4.;
+impl Drawable for RobinsonB {
+    fn draw(&self, draw: &nannou::draw::Draw, xoff: f32, yoff: f32, scale: f32, props: &DrawProps) {
+        let pts = self.polygon(xoff, yoff, scale);
+        let points = (0..pts.len()).map(|i| {
+            pt2(pts[i].0, pts[i].1)
+        });
+        draw.polygon()
+            .color(props.fill_color1)
+            .stroke(props.edge_color)
+            .stroke_weight(props.edge_weight)
+            .join_miter()
+            .points(points);
 
-        let p = self.polygon(xoff, yoff, scale);
-        let a0 = interpolate(p[2], p[1], t3);
-        let a1 = interpolate(p[2], p[1], t1);
-        let a2 = interpolate(p[2], p[3], t1);
-        let a3 = interpolate(p[2], p[3], t3);
+        if (props.show_arcs) {
+            let a1 = self.get_small_arc();
+            let angles = interp_angles(a1.start_angle, a1.end_angle);
+            let radius = a1.radius;
+            let arc_points = angles.iter().map(|a| {
+                pt2(xoff + scale*(a1.center.0 + radius * a.cos()) as f32,
+                    yoff + scale*(a1.center.1 + radius * a.sin()) as f32)
+            });
+            draw.polyline()
+                .color(props.arc1_color)
+                .stroke_weight(2.)
+                .points(arc_points);
 
-        let mut result = Vec::new();
-        result.push(a0); result.push(a2);
-        result.push(a1); result.push(a3);
+            //
+            let a2 = self.get_big_arc();
+            let angles = interp_angles(a2.start_angle, a2.end_angle);
+            let radius = a2.radius;
+            let arc_points = angles.iter().map(|a| {
+                pt2(xoff + scale*(a2.center.0 + radius * a.cos()) as f32,
+                    yoff + scale*(a2.center.1 + radius * a.sin()) as f32)
+            });
+            draw.polyline()
+                .color(props.arc2_color)
+                .stroke_weight(2.)
+                .points(arc_points);
+        }
+    }
 
-        return result
+    fn append_to_vector(&self, dst: &mut Vec<Box<dyn Drawable>>, dx: f64, dy: f64) {
+        dst.push(Box::new(self.translate(dx, dy)));
     }
 }
 
-fn build_tile(tile: &penrose::Tile, x: f64, y: f64, angle: i32) -> Result<Box<dyn Drawable>, i32> {
+// Robinson triangles are mirrored by kind, so `reflected` only applies to
+// the whole tiles.
+fn build_tile(tile: &penrose::Tile, x: f64, y: f64, angle: i32, reflected: bool) -> Result<Box<dyn Drawable>, i32> {
     match tile {
-        penrose::Tile::DART => Ok(Box::new(Dart::new(x, y, angle))),
-        penrose::Tile::KITE => Ok(Box::new(Kite::new(x, y, angle))),
-        penrose::Tile::FAT => Ok(Box::new(Fat::new(x, y, angle))),
-        penrose::Tile::SKINNY => Ok(Box::new(Skinny::new(x, y, angle))),
+        penrose::Tile::DART => Ok(Box::new(Dart { reflected, ..Dart::new(x, y, angle) })),
+        penrose::Tile::KITE => Ok(Box::new(Kite { reflected, ..Kite::new(x, y, angle) })),
+        penrose::Tile::FAT => Ok(Box::new(Fat { reflected, ..Fat::new(x, y, angle) })),
+        penrose::Tile::SKINNY => Ok(Box::new(Skinny { reflected, ..Skinny::new(x, y, angle) })),
+        penrose::Tile::ROBINSON_A => Ok(Box::new(RobinsonA::new(x, y, angle))),
+        penrose::Tile::ROBINSON_A_MIRROR => Ok(Box::new(RobinsonA::new(x, y, angle).mirror())),
+        penrose::Tile::ROBINSON_B => Ok(Box::new(RobinsonB::new(x, y, angle))),
+        penrose::Tile::ROBINSON_B_MIRROR => Ok(Box::new(RobinsonB::new(x, y, angle).mirror())),
     }
 }
 
+// A drawable copy of a library tile.
+fn drawable(t: &dyn PenroseTile) -> Result<Box<dyn Drawable>, i32> {
+    build_tile(&t.kind(), t.center().0, t.center().1, t.angle(), t.reflected())
+}
+
+// The library tile for a drawn tile moved by `offset`.
+fn library_tile(t: &dyn Drawable, offset: (f64, f64)) -> Box<dyn PenroseTile> {
+    let tile = new_tile(t.kind(), t.center().0 + offset.0, t.center().1 + offset.1, t.angle());
+    if t.reflected() { reflect_tile(tile.as_ref()) } else { tile }
+}
+
+fn library_tiles(model: &Model) -> Vec<Box<dyn PenroseTile>> {
+    model.tiles.iter().map(|t| library_tile(t.as_ref(), (0., 0.))).collect()
+}
+
 fn interp_angles(start_angle: i32, end_angle: i32) -> Vec<f64> {
     let mut result = Vec::new();
 
@@ -400,123 +387,46 @@ fn interp_angles(start_angle: i32, end_angle: i32) -> Vec<f64> {
     return result
 }
 
-// fn build_vertex1(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let phi = (1. + 5_f64.sqrt())/2.;
-//     let d1 = Dart::new(x - phi, y, angle);
-//     let d2 = place_dart_edge(3, d1.edge_center(2)?, d1.edge_angle(2)?);
-//     let d3 = place_dart_edge(3, d2.edge_center(2)?, d2.edge_angle(2)?);
-//     let d4 = place_dart_edge(3, d3.edge_center(2)?, d3.edge_angle(2)?);
-//     let d5 = place_dart_edge(3, d4.edge_center(2)?, d4.edge_angle(2)?);
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(d2));
-//     tiles.push(Box::new(d3));
-//     tiles.push(Box::new(d4));
-//     tiles.push(Box::new(d5));
-//     Ok(tiles)
-// }
-
-// fn build_vertex2(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let d1 = Dart::new(x, y, angle);
-//     let k1 = place_kite_edge(2, d1.edge_center(4)?, d1.edge_angle(4)?);
-//     let k2 = place_kite_edge(4, k1.edge_center(1)?, k1.edge_angle(1)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(k2));
-//     Ok(tiles)
-// }
-
-// fn build_vertex3(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let phi = (1. + 5_f64.sqrt())/2.;
-
-//     let k1 = Kite::new(x + phi, y, angle);
-//     let k2 = place_kite_edge(1, k1.edge_center(4)?, k1.edge_angle(4)?);
-//     let k3 = place_kite_edge(1, k2.edge_center(4)?, k2.edge_angle(4)?);
-//     let k4 = place_kite_edge(1, k3.edge_center(4)?, k3.edge_angle(4)?);
-//     let k5 = place_kite_edge(1, k4.edge_center(4)?, k4.edge_angle(4)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(k2));
-//     tiles.push(Box::new(k3));
-//     tiles.push(Box::new(k4));
-//     tiles.push(Box::new(k5));
-//     Ok(tiles)
-// }
-
-// fn build_vertex4(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let phi = (1. + 5_f64.sqrt())/2.;
-
-//     let d1 = Dart::new(x - phi, y, angle);
-//     let d2 = place_dart_edge(3, d1.edge_center(2)?, d1.edge_angle(2)?);
-//     let k1 = place_kite_edge(1, d2.edge_center(2)?, d2.edge_angle(2)?);
-//     let k2 = place_kite_edge(1, k1.edge_center(4)?, k1.edge_angle(4)?);
-//     let d3 = place_dart_edge(3, k2.edge_center(4)?, k2.edge_angle(4)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(d2));
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(k2));
-//     tiles.push(Box::new(d3));
-//     Ok(tiles)
-// }
-
-// fn build_vertex5(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-
-//     let k1 = Kite::new(x - 1., y, angle);
-//     let d1 = place_dart_edge(4, k1.edge_center(2)?, k1.edge_angle(2)?);
-//     let k2 = place_kite_edge(1, d1.edge_center(3)?, d1.edge_angle(3)?);
-//     let k3 = place_kite_edge(1, k2.edge_center(4)?, k2.edge_angle(4)?);
-//     let d2 = place_dart_edge(2, k3.edge_center(4)?, k3.edge_angle(4)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(k2));
-//     tiles.push(Box::new(k3));
-//     tiles.push(Box::new(d2));
-//     Ok(tiles)
-// }
-
-// fn build_vertex6(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let phi = (1. + 5_f64.sqrt())/2.;
-
-//     let d1 = Dart::new(x - phi, y, angle);
-//     let k1 = place_kite_edge(4, d1.edge_center(2)?, d1.edge_angle(2)?);
-//     let k2 = place_kite_edge(2, k1.edge_center(3)?, k1.edge_angle(3)?);
-//     let k3 = place_kite_edge(4, k2.edge_center(1)?, k2.edge_angle(1)?);
-//     let k4 = place_kite_edge(2, k3.edge_center(3)?, k3.edge_angle(3)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(k2));
-//     tiles.push(Box::new(k3));
-//     tiles.push(Box::new(k4));
-//     Ok(tiles)
-// }
-
-// fn build_vertex7(x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
-//     let s5 = 5_f64.sqrt();
-//     let phi = (1.+s5)/2.;
-//     let k = (2.+s5) / (1.+s5);
-//     let p = (10. + 20_f64.sqrt()).sqrt()/4.;
-
-//     let k1 = Kite::new(x + k - 1., y - p, angle);
-//     let k2 = place_kite_edge(3, k1.edge_center(2)?, k1.edge_angle(2)?);
-//     let d1 = place_dart_edge(4, k2.edge_center(2)?, k2.edge_angle(2)?);
-//     let d2 = place_dart_edge(2, d1.edge_center(3)?, d1.edge_angle(3)?);
-
-//     let mut tiles: Vec<Box<dyn Drawable>> = Vec::new();
-//     tiles.push(Box::new(k1));
-//     tiles.push(Box::new(k2));
-//     tiles.push(Box::new(d1));
-//     tiles.push(Box::new(d2));
-//     Ok(tiles)
-// }
+// A group of tiles around a vertex, placed in one go.
+#[derive(Clone, Copy)]
+enum VertexStamp {
+    KiteDart(penrose::VertexType),
+    Rhombus(penrose::RhombusVertexType),
+}
+
+// The kite and dart vertices in the order of the number keys.
+const KITE_DART_VERTICES: [penrose::VertexType; 7] = [
+    penrose::VertexType::Star, penrose::VertexType::Ace, penrose::VertexType::Sun,
+    penrose::VertexType::King, penrose::VertexType::Jack, penrose::VertexType::Queen,
+    penrose::VertexType::Deuce,
+];
+
+fn build_vertex(vertex: VertexStamp, x: f64, y: f64, angle: i32) -> Result<Vec<Box<dyn Drawable>>, i32> {
+    let tiles = match vertex {
+        VertexStamp::KiteDart(v) => v.tiles((x, y), angle),
+        VertexStamp::Rhombus(v) => v.tiles((x, y), angle),
+    }.ok_or(angle)?;
+    tiles.iter()
+        .map(|t| drawable(t.as_ref()))
+        .collect()
+}
+
+// Number key `n` picks a vertex of the current tile set.
+fn select_vertex(model: &mut Model, n: usize) {
+    model.vertex_type = match model.next_tile {
+        penrose::Tile::FAT | penrose::Tile::SKINNY =>
+            penrose::RhombusVertexType::all().get(n).map(|v| VertexStamp::Rhombus(*v)),
+        _ => KITE_DART_VERTICES.get(n).map(|v| VertexStamp::KiteDart(*v)),
+    }
+}
+
+// The tile or vertex group that the next click places.
+fn build_next(model: &Model, x: f64, y: f64) -> Result<Vec<Box<dyn Drawable>>, i32> {
+    match model.vertex_type {
+        Some(v) => build_vertex(v, x, y, model.angle),
+        None => Ok(vec![build_tile(&model.next_tile, x, y, model.angle, model.reflected)?]),
+    }
+}
 
 fn main() {
     nannou::app(model)
@@ -528,7 +438,9 @@ fn main() {
 
 struct Model {
     tiles: Vec<Box<dyn Drawable>>,
-    edges: Vec<penrose::Edge>,
+    mesh: Mesh,
+    // The number of tiles each placement added, for undo.
+    undo: Vec<usize>,
     current_point: Point2,
     show_edges: bool,
     show_arcs: bool,
@@ -536,7 +448,13 @@ struct Model {
     scale: f64,
     debug: bool,
     next_tile: penrose::Tile,
+    // Whether the next kite, dart or rhombus is placed reflected.
+    reflected: bool,
+    vertex_type: Option<VertexStamp>,
     angle: i32,
+    // How far the whole patch is turned on screen, in degrees.
+    rotation: f64,
+    dead_ends: Vec<(f64, f64)>,
 }
 
 fn snap_tolerance(scale: f64) -> f64 {
@@ -570,7 +488,8 @@ fn model(app: &App) -> Model {
         .build()
         .unwrap();
     Model { tiles: Vec::new(),
-            edges: Vec::new(),
+            mesh: Mesh::new(),
+            undo: Vec::new(),
             current_point: pt2(0.,0.),
             show_edges: true,
             show_arcs: true,
@@ -578,7 +497,11 @@ fn model(app: &App) -> Model {
             scale: 25.,
             debug: false,
             next_tile: penrose::Tile::DART,
+            reflected: false,
+            vertex_type: None,
             angle: 0,
+            rotation: 0.,
+            dead_ends: Vec::new(),
     }
 }
 
@@ -586,18 +509,15 @@ fn snap_to_edges(tile: &Box<dyn Drawable>, edges: &Vec<Edge>, tol: f64) -> (f64,
     let mut result = (0., 0.);
     let mut curr_l2 = tol;
 
-    let e1 = tile.get_drawable_edges();
+    let e1 = tile.get_edges();
     for e in edges {
 
-        for i in 0..4 {
+        for i in 0..e1.len() {
             let dx = e.center.0 - e1[i].center.0;
             let dy = e.center.1 - e1[i].center.1;
             let l2 = dx*dx + dy*dy;
             if (l2 < curr_l2) {
-                if ((e1[i].angle + 180) % 360 != e.angle) {
-                    continue;
-                }
-                if (e1[i].length != e.length) {
+                if (!edges_match(&e1[i], e)) {
                     continue;
                 }
 
@@ -612,11 +532,11 @@ fn snap_to_edges(tile: &Box<dyn Drawable>, edges: &Vec<Edge>, tol: f64) -> (f64,
 
 fn snaps(edges: &Vec<penrose::Edge>, tile: &Box<dyn Drawable>, tol: f64) -> bool {
 
-    let de = tile.get_drawable_edges();
+    let de = tile.get_edges();
 
     for edge in edges {
-        for i in 0..4 {
-            if (edge.angle + 180)%360 != de[i].angle {
+        for i in 0..de.len() {
+            if !edges_match(&de[i], edge) {
                 continue;
             }
 
@@ -631,86 +551,312 @@ fn snaps(edges: &Vec<penrose::Edge>, tile: &Box<dyn Drawable>, tol: f64) -> bool
     return false
 }
 
-fn match_edges(t1: &Box<dyn Drawable>, t2: &Box<dyn Drawable>, skip: [bool; 4]) -> [bool; 4] {
-    let mut result = skip;
-    let e1 = t1.get_drawable_edges();
-    let e2 = t2.get_drawable_edges();
+// Start again from a patch that has been replaced as a whole, which can't
+// be undone tile by tile.
+fn rebuild_mesh(model: &mut Model) {
+    let tiles = library_tiles(model);
+    model.mesh = Mesh::from_tiles(&tiles);
+    // Every gap on the boundary is new.
+    model.dead_ends = dead_ends_near(&model.mesh, &boundary_vertices(model), VERTEX_REACH);
+    model.undo.clear();
+}
 
-    for i in 0..4 {
-        if result[i] { continue }
+// Far enough from a boundary vertex to find its gap, and well short of the
+// next vertex.
+const VERTEX_REACH: f64 = 0.1;
 
-        for j in 0..4 {
-            let dx = e2[j].center.0 - e1[i].center.0;
-            let dy = e2[j].center.1 - e1[i].center.1;
-            let l2 = dx*dx + dy*dy;
-            if (l2 > 0.1) {
-                continue;
-            }
+// The vertices at the ends of the open edges, where the gaps are.
+fn boundary_vertices(model: &Model) -> Vec<(f64, f64)> {
+    model.mesh.boundary_edges().iter().map(|e| e.end_points().1).collect()
+}
 
-            let a1 = (e1[i].angle + 180) % 360;
-            let a2 = e2[j].angle;
-            if (a1 != a2) {
-                continue;
+// How far from a changed tile the gaps it can change are: the tiles that
+// could fill them reach a few edge lengths.
+const CHANGE_REACH: f64 = 10.;
+
+// Recheck the dead ends near the tiles at `changed`, after adding or
+// removing them.
+fn update_dead_ends(model: &mut Model, changed: &[(f64, f64)]) {
+    let near = |p: &(f64, f64)| changed.iter().any(|c| (p.0 - c.0).hypot(p.1 - c.1) < CHANGE_REACH);
+    model.dead_ends.retain(|p| !near(p));
+    model.dead_ends.extend(dead_ends_near(&model.mesh, changed, CHANGE_REACH));
+}
+
+// The open edges of the patch within `r` of `p`.
+fn edges_near(model: &Model, p: (f64, f64), r: f64) -> Vec<Edge> {
+    model.mesh.boundary_near(p, r).iter().map(|h| model.mesh.edge(*h)).collect()
+}
+
+// How far from a tile's centre the edges it can snap to are.
+fn snap_reach(model: &Model) -> f64 {
+    snap_tolerance(model.scale).sqrt() + 2.
+}
+
+// The offset that snaps a group of tiles, taken from whichever of its tiles
+// is nearest to a matching edge of the patch.
+fn snap_offset(model: &Model, tiles: &[Box<dyn Drawable>]) -> (f64, f64) {
+    tiles.iter()
+        .map(|t| snap_to_edges(t, &edges_near(model, t.center(), snap_reach(model)), snap_tolerance(model.scale)))
+        .filter(|o| *o != (0., 0.))
+        .min_by(|a, b| a.0.hypot(a.1).partial_cmp(&b.0.hypot(b.1)).unwrap())
+        .unwrap_or((0., 0.))
+}
+
+fn snapped_conflicts(model: &Model, tiles: &[Box<dyn Drawable>]) -> Vec<i32> {
+    let offset = snap_offset(model, tiles);
+    let mut result = Vec::new();
+    for tile in tiles {
+        let placed = library_tile(tile.as_ref(), offset);
+        result.extend(placement_conflicts(placed.as_ref(), &edges_near(model, placed.center(), 3.), 1e-6));
+    }
+    result
+}
+
+fn add_tiles(model: &mut Model, tiles: Vec<Box<dyn Drawable>>) {
+
+    let conflicts = snapped_conflicts(model, &tiles);
+    if !conflicts.is_empty() {
+        println!("Illegal placement: edges {:?} break the matching rules", conflicts);
+        return
+    }
+
+    let offset = snap_offset(model, &tiles);
+    push_tiles(model, tiles, offset);
+}
+
+// Add tiles moved by `offset` to the patch as one placement. The mesh
+// glues them on and updates the boundary as it goes.
+fn push_tiles(model: &mut Model, tiles: Vec<Box<dyn Drawable>>, offset: (f64, f64)) {
+    let mut changed = Vec::new();
+    for tile in tiles {
+        tile.append_to_vector(&mut model.tiles, offset.0, offset.1);
+        if let Some(t) = model.tiles.last() {
+            model.mesh.add_tile(library_tile(t.as_ref(), (0., 0.)).as_ref());
+            changed.push(t.center());
+        }
+    }
+    model.undo.push(changed.len());
+    update_dead_ends(model, &changed);
+}
+
+// Take off the tiles of the last placement, which puts the boundary back
+// as it was. After the patch has been replaced there is nothing to go back
+// to, so take off one tile at a time.
+fn undo_placement(model: &mut Model) {
+    let n = model.undo.pop().unwrap_or(1);
+    let mut changed = Vec::new();
+    for _ in 0..n {
+        model.tiles.pop();
+        if let Some(t) = model.mesh.pop_tile() {
+            changed.push(t.center());
+        }
+    }
+    update_dead_ends(model, &changed);
+}
+
+fn place_forced_tiles(model: &mut Model) {
+    // Placing forced tiles can force more, so keep going until none are left.
+    // After the first pass only the gaps near the tiles just placed can have
+    // changed.
+    let mut forced = forced_tiles_near(&model.mesh, &boundary_vertices(model), VERTEX_REACH);
+    for _ in 0..100 {
+        if forced.is_empty() {
+            break
+        }
+        // Tiles forced at different vertices may clash if the patch is at a
+        // dead end; only place the first of those.
+        let mut placed: Vec<Box<dyn PenroseTile>> = Vec::new();
+        let mut new_tiles = Vec::new();
+        for t in forced {
+            if overlaps(t.as_ref(), &placed) {
+                continue
+            }
+            match drawable(t.as_ref()) {
+                Ok(d) => new_tiles.push(d),
+                Err(_) => println!("Error building forced tile"),
             }
-            result[i] = true;
+            placed.push(t);
+        }
+        push_tiles(model, new_tiles, (0., 0.));
+        let changed: Vec<(f64, f64)> = placed.iter().map(|t| t.center()).collect();
+        forced = forced_tiles_near(&model.mesh, &changed, CHANGE_REACH);
+    }
+}
+
+fn deflate_tiles(model: &mut Model) {
+    let tiles = library_tiles(model);
+
+    let mut new_tiles = Vec::new();
+    for t in deflate(&tiles) {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building deflated tile"),
         }
     }
-    return result;
+    model.tiles = new_tiles;
+    rebuild_mesh(model);
+
+    // Deflation grows the patch by phi; zoom out to keep it the same size.
+    let phi = (1. + 5_f64.sqrt())/2.;
+    model.scale = model.scale / phi;
 }
 
-fn add_tile(model: &mut Model, tile: Box<dyn Drawable>) {
+fn inflate_tiles(model: &mut Model) {
+    let tiles = library_tiles(model);
 
-    let offset = snap_to_edges(&tile, &model.edges.clone(), snap_tolerance(model.scale));
-    tile.append_to_vector(&mut model.tiles, offset.0, offset.1);
+    let inflation = inflate(&tiles);
+    if !inflation.uncomposed.is_empty() {
+        println!("{} tiles could not be composed", inflation.uncomposed.len());
+    }
 
-    let mut new_edges = Vec::new();
-    for t1 in &model.tiles {
-        let mut matches = [false, false, false, false];
-        for t2 in &model.tiles {
-            // @todo don't need to check tile against itself
-            // if (t1 == t2) {
-            //     continue;
-            // }
-            matches = match_edges(&t1, &t2, matches);
+    let mut new_tiles = Vec::new();
+    for t in inflation.tiles {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building inflated tile"),
         }
+    }
+    model.tiles = new_tiles;
+    rebuild_mesh(model);
 
-        let e = t1.get_drawable_edges();
-        for i in 0..4 {
-            if (!matches[i]) {
-                new_edges.push(Edge {center: (e[i].center.0, e[i].center.1),
-                                     angle: e[i].angle,
-                                     length: e[i].length } );
-            }
+    // Inflation shrinks the patch by phi; zoom in to keep it the same size.
+    let phi = (1. + 5_f64.sqrt())/2.;
+    model.scale = model.scale * phi;
+}
+
+fn convert_tiles(model: &mut Model) {
+    let tiles = library_tiles(model);
+
+    let rhombi = tiles.iter().any(|t| t.kind() == penrose::Tile::FAT || t.kind() == penrose::Tile::SKINNY);
+    let converted = if rhombi { to_kites_and_darts(&tiles) } else { to_rhombi(&tiles) };
+
+    let mut new_tiles = Vec::new();
+    for t in converted {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building converted tile"),
         }
     }
-    model.edges = new_edges;
+    model.tiles = new_tiles;
+    rebuild_mesh(model);
 }
 
-fn pop_last_tile(model: &mut Model) {
+fn triangle_tiles(model: &mut Model) {
+    let tiles = library_tiles(model);
 
-    model.tiles.pop();
+    let triangles = tiles.iter().any(|t| t.kind().is_triangle());
+    let converted = if triangles { from_triangles(&tiles) } else { to_triangles(&tiles) };
 
-    let mut new_edges = Vec::new();
-    for t1 in &model.tiles {
-        let mut matches = [false, false, false, false];
-        for t2 in &model.tiles {
-            // @todo don't need to check tile against itself
-            // if (t1 == t2) {
-            //     continue;
-            // }
-            matches = match_edges(&t1, &t2, matches);
+    let mut new_tiles = Vec::new();
+    for t in converted {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building converted tile"),
         }
+    }
+    model.tiles = new_tiles;
+    rebuild_mesh(model);
+}
 
-        let e = t1.get_drawable_edges();
-        for i in 0..4 {
-            if (!matches[i]) {
-                new_edges.push(Edge {center: (e[i].center.0, e[i].center.1),
-                                     angle: e[i].angle,
-                                     length: e[i].length } );
-            }
+fn pentagrid_tiles(model: &mut Model) {
+    // Fill the window with a rhombus tiling from the pentagrid.
+    let region = BoundingBox::around((0., 0.), 360. / model.scale);
+    let mut new_tiles = Vec::new();
+    for t in pentagrid([0.1, 0.37, -0.24, 0.02, -0.25], &region) {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building pentagrid tile"),
+        }
+    }
+    model.tiles = new_tiles;
+    rebuild_mesh(model);
+}
+
+const PATCH_FILE: &str = "patch.json";
+
+fn view_settings(model: &Model) -> ViewSettings {
+    ViewSettings {
+        scale: model.scale,
+        show_edges: model.show_edges,
+        show_arcs: model.show_arcs,
+        show_bars: model.show_bars,
+        rotation: model.rotation,
+    }
+}
+
+fn save_tiles(model: &Model) {
+    let tiles = library_tiles(model);
+    match save_patch(PATCH_FILE, &tiles, &view_settings(model)) {
+        Ok(()) => println!("Saved {} tiles to {}", tiles.len(), PATCH_FILE),
+        Err(e) => println!("Error saving {}: {}", PATCH_FILE, e),
+    }
+}
+
+fn load_tiles(model: &mut Model, path: &std::path::Path) {
+    let file = match load_patch(path) {
+        Ok(file) => file,
+        Err(e) => {
+            println!("Error loading {}: {}", path.display(), e);
+            return
+        }
+    };
+    let mut new_tiles = Vec::new();
+    for t in file.tiles {
+        match drawable(t.as_ref()) {
+            Ok(d) => new_tiles.push(d),
+            Err(_) => println!("Error building loaded tile"),
+        }
+    }
+    model.tiles = new_tiles;
+    model.scale = file.view.scale;
+    model.show_edges = file.view.show_edges;
+    model.show_arcs = file.view.show_arcs;
+    model.show_bars = file.view.show_bars;
+    model.rotation = file.view.rotation;
+    rebuild_mesh(model);
+}
+
+const SVG_FILE: &str = "patch.svg";
+
+// The export style that draws the patch as the window does.
+fn export_style(model: &Model) -> Style {
+    let props = tile_props(model);
+    let rgb = |c: nannou::color::Srgb<u8>| (c.red, c.green, c.blue);
+    Style {
+        background: Some(rgb(CORNFLOWERBLUE)),
+        fill_color1: rgb(props.fill_color1),
+        fill_color2: rgb(props.fill_color2),
+        edge_color: rgb(props.edge_color),
+        arc1_color: rgb(props.arc1_color),
+        arc2_color: rgb(props.arc2_color),
+        bar_color: rgb(props.bar_color),
+        edge_weight: props.edge_weight as f64,
+        show_arcs: props.show_arcs,
+        show_bars: props.show_bars,
+        ..Style::default()
+    }
+}
+
+fn export_svg(model: &Model) {
+    let tiles = library_tiles(model);
+    match save_svg(SVG_FILE, &tiles, &RigidTransform::rotation(model.rotation), model.scale, &export_style(model)) {
+        Ok(()) => println!("Exported {} tiles to {}", tiles.len(), SVG_FILE),
+        Err(e) => println!("Error exporting {}: {}", SVG_FILE, e),
+    }
+}
+
+// Triangles are mirrored by kind, and whole tiles by their reflected flag.
+fn mirror_next_tile(model: &mut Model) {
+    model.next_tile = match model.next_tile {
+        penrose::Tile::ROBINSON_A => penrose::Tile::ROBINSON_A_MIRROR,
+        penrose::Tile::ROBINSON_A_MIRROR => penrose::Tile::ROBINSON_A,
+        penrose::Tile::ROBINSON_B => penrose::Tile::ROBINSON_B_MIRROR,
+        penrose::Tile::ROBINSON_B_MIRROR => penrose::Tile::ROBINSON_B,
+        other => {
+            model.reflected = !model.reflected;
+            other
         }
     }
-    model.edges = new_edges;
 }
 
 fn event(_app: &App, _model: &mut Model, event: Event) {
@@ -729,15 +875,9 @@ fn event(_app: &App, _model: &mut Model, event: Event) {
 
 fn update(_app: &App, _model: &mut Model, _update: Update) {}
 
-fn view(app: &App, model: &Model, frame: Frame) {
-
-    // Begin drawing
-    let draw: nannou::draw::Draw = app.draw();
-
-    // Clear the background to blue.
-    draw.background().color(CORNFLOWERBLUE);
-
-    let tile_props = DrawProps {
+// How placed tiles are drawn.
+fn tile_props(model: &Model) -> DrawProps {
+    DrawProps {
         fill_color1: LEMONCHIFFON,
         fill_color2: LINEN,
         edge_color: LAVENDER,
@@ -747,7 +887,27 @@ fn view(app: &App, model: &Model, frame: Frame) {
         edge_weight: if model.show_edges { 2. } else { 0. },
         show_arcs: model.show_arcs,
         show_bars: model.show_bars,
-    };
+    }
+}
+
+// The point of the patch under the mouse.
+fn mouse_point(model: &Model) -> (f64, f64) {
+    let p = (model.current_point.x as f64 / model.scale, model.current_point.y as f64 / model.scale);
+    RigidTransform::rotation(model.rotation).inverse().apply(p)
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+
+    // Begin drawing
+    let window_draw: nannou::draw::Draw = app.draw();
+
+    // Clear the background to blue.
+    window_draw.background().color(CORNFLOWERBLUE);
+
+    // Everything else is drawn in the patch's frame, turned as a whole.
+    let draw = window_draw.rotate(model.rotation.to_radians() as f32);
+
+    let tile_props = tile_props(model);
 
     let drag_props = DrawProps {
         fill_color1: GAINSBORO,
@@ -773,14 +933,34 @@ fn view(app: &App, model: &Model, frame: Frame) {
         show_bars: false,
     };
 
+    let illegal_props = DrawProps {
+        fill_color1: LIGHTCORAL,
+        fill_color2: LIGHTCORAL,
+        edge_color: PINK,
+        arc1_color: LIGHTPINK,
+        arc2_color: PALEGREEN,
+        bar_color: LIGHTSTEELBLUE,
+        edge_weight: 0.,
+        show_arcs: model.show_arcs,
+        show_bars: false,
+    };
+
     // Draw the tiles
     for t in &model.tiles {
         t.draw(&draw, 0., 0., model.scale as f32, &tile_props);
     }
 
+    // Highlight the dead ends
+    for p in &model.dead_ends {
+        draw.ellipse()
+            .x_y((p.0 * model.scale) as f32, (p.1 * model.scale) as f32)
+            .radius(6.)
+            .color(RED);
+    }
+
     // DEBUGGING: Draw the edges
     if (model.debug) {
-        for e in &model.edges {
+        for e in &model.mesh.boundary_edges() {
             let angle_in_radians = e.angle as f64 * std::f64::consts::PI / 180.0f64;
             let r = (model.scale as f64) * (if e.length == EdgeLength::SHORT { 1.0f64 } else { 1.6f64 });
             let v = Vector2::<f32>::new((r*angle_in_radians.cos()) as f32,
@@ -795,20 +975,22 @@ fn view(app: &App, model: &Model, frame: Frame) {
         }
     }
 
-    // Draw currently dragged tile
-    let x = model.current_point.x as f64 / model.scale;
-    let y = model.current_point.y as f64 / model.scale;
-    let tmp = build_tile(&model.next_tile, x, y, model.angle);
-    match tmp {
-        Ok(t) => {
-            let props = if snaps(&model.edges, &t, snap_tolerance(model.scale)) { &snap_props } else { &drag_props };
-            t.draw(&draw, 0., 0., model.scale as f32, props)
+    // Draw currently dragged tile, or group of tiles around a vertex
+    let (x, y) = mouse_point(model);
+    match build_next(model, x, y) {
+        Ok(tiles) => {
+            let props = if !snapped_conflicts(model, &tiles).is_empty() { &illegal_props }
+                        else if tiles.iter().any(|t| snaps(&edges_near(model, t.center(), snap_reach(model)), t, snap_tolerance(model.scale))) { &snap_props }
+                        else { &drag_props };
+            for t in tiles.iter() {
+                t.draw(&draw, 0., 0., model.scale as f32, props)
+            }
         },
         Err(_) => println!("Error drawing current tile"),
     }
 
     // Write the result of our drawing to the window's frame.
-    draw.to_frame(app, &frame).unwrap();
+    window_draw.to_frame(app, &frame).unwrap();
 }
 
 use nannou::event::*;
@@ -817,53 +999,51 @@ fn window_event(_app: &App, model: &mut Model, event: WindowEvent) {
     match event {
         KeyPressed(key) => {
             match key {
-                // Key::Key1 => model.vertex_type = 1,
-                // Key::Key2 => model.vertex_type = 2,
-                // Key::Key3 => model.vertex_type = 3,
-                // Key::Key4 => model.vertex_type = 4,
-                // Key::Key5 => model.vertex_type = 5,
-                // Key::Key6 => model.vertex_type = 6,
-                // Key::Key7 => model.vertex_type = 7,
+                Key::Key1 => select_vertex(model, 0),
+                Key::Key2 => select_vertex(model, 1),
+                Key::Key3 => select_vertex(model, 2),
+                Key::Key4 => select_vertex(model, 3),
+                Key::Key5 => select_vertex(model, 4),
+                Key::Key6 => select_vertex(model, 5),
+                Key::Key7 => select_vertex(model, 6),
+                Key::Key8 => select_vertex(model, 7),
                 Key::A => model.show_arcs = !model.show_arcs,
                 Key::B => model.show_bars = !model.show_bars,
-                Key::C => { model.tiles = Vec::new(); model.edges = Vec::new(); },
+                Key::C => { model.tiles = Vec::new(); rebuild_mesh(model) },
                 Key::E => model.show_edges = !model.show_edges,
-                Key::D => model.next_tile = penrose::Tile::DART,
-                Key::K => model.next_tile = penrose::Tile::KITE,
-                Key::S => model.next_tile = penrose::Tile::SKINNY,
-                Key::F => model.next_tile = penrose::Tile::FAT,
+                Key::D => { model.next_tile = penrose::Tile::DART; model.vertex_type = None },
+                Key::K => { model.next_tile = penrose::Tile::KITE; model.vertex_type = None },
+                Key::S => { model.next_tile = penrose::Tile::SKINNY; model.vertex_type = None },
+                Key::F => { model.next_tile = penrose::Tile::FAT; model.vertex_type = None },
+                Key::H => { model.next_tile = penrose::Tile::ROBINSON_A; model.vertex_type = None },
+                Key::J => { model.next_tile = penrose::Tile::ROBINSON_B; model.vertex_type = None },
+                Key::M => mirror_next_tile(model),
+                Key::R => triangle_tiles(model),
+                Key::P => pentagrid_tiles(model),
+                Key::Z => place_forced_tiles(model),
+                Key::G => deflate_tiles(model),
+                Key::I => inflate_tiles(model),
+                Key::T => convert_tiles(model),
                 Key::X => model.debug = !model.debug,
-                Key::U => pop_last_tile(model),
+                Key::U => undo_placement(model),
+                Key::V => export_svg(model),
+                Key::W => save_tiles(model),
                 Key::Up => { model.scale = 2.*model.scale.min(100.) },
                 Key::Down => { model.scale = 0.5*model.scale.max(1.) },
                 Key::Left => { model.angle = (model.angle + 36) % 360 },
                 Key::Right => { model.angle = (model.angle + 360 - 36) % 360 },
+                Key::LBracket => { model.rotation = (model.rotation + 5.).rem_euclid(360.) },
+                Key::RBracket => { model.rotation = (model.rotation - 5.).rem_euclid(360.) },
                 _ => println!("KeyPressed = {:?}", key),
             }
         }
         KeyReleased(_key) => {}
         MouseMoved(pos) => { model.current_point = pos }
         MousePressed(_button) => {
-            let x = model.current_point.x as f64 / model.scale;
-            let y = model.current_point.y as f64 / model.scale;
-            // let res = match model.vertex_type {
-            //     1 => build_vertex1(x, y, model.angle),
-            //     2 => build_vertex2(x, y, model.angle),
-            //     3 => build_vertex3(x, y, model.angle),
-            //     4 => build_vertex4(x, y, model.angle),
-            //     5 => build_vertex5(x, y, model.angle),
-            //     6 => build_vertex6(x, y, model.angle),
-            //     7 => build_vertex7(x, y, model.angle),
-            //     _ => Err(666),
-            // };
-            // match res {
-            //     Ok(t) => for o in t { model.tiles.push(o) },
-            //     Err(_) => println!("Error building vertex 2"),
-            // }
-            let res = build_tile(&model.next_tile, x, y, model.angle);
-            match res {
-                Ok(t) => add_tile(model, t),
-                Err(_) => println!("Error building vertex 2"),
+            let (x, y) = mouse_point(model);
+            match build_next(model, x, y) {
+                Ok(tiles) => add_tiles(model, tiles),
+                Err(_) => println!("Error building tile"),
             }
         }
         MouseReleased(_button) => {}
@@ -919,4 +1099,6 @@ fn hovered_file(_app: &App, _model: &mut Model, _path: std::path::PathBuf) {}
 
 fn hovered_file_cancelled(_app: &App, _model: &mut Model) {}
 
-fn dropped_file(_app: &App, _model: &mut Model, _path: std::path::PathBuf) {}
+fn dropped_file(_app: &App, model: &mut Model, path: std::path::PathBuf) {
+    load_tiles(model, &path)
+}
@@ -0,0 +1,546 @@
+//! Patch topology as a half-edge mesh.
+//!
+//! Each tile is a face bounded by a loop of half-edges, one per tile edge,
+//! and tiles that share a corner share the vertex. When a tile is added its
+//! half-edges are glued to the half-edges running the other way between the
+//! same vertices, so neighbours, the open boundary and the tiles around a
+//! vertex can be read off by following links rather than by comparing edge
+//! positions.
+//!
+//! Vertices are only matched by position when a tile is added, to within
+//! `VERTEX_TOLERANCE`, which is far below the distance between any two
+//! distinct vertices. The tiles and the open edges are kept in spatial
+//! hashes as well, so that adding or removing a tile, and finding what is
+//! near a point, doesn't depend on the size of the patch.
+
+use std::collections::HashMap;
+
+use crate::exact::ExactPoint;
+use crate::spatial::SpatialHash;
+use crate::tiles::*;
+
+/// How close two corners must be to be the same vertex.
+pub const VERTEX_TOLERANCE: f64 = 1e-6;
+
+pub struct MeshVertex {
+    pub position: (f64, f64),
+    /// The exact position, if the first tile to use the vertex had one.
+    pub exact: Option<ExactPoint>,
+    /// A half-edge that starts here.
+    pub half_edge: usize,
+}
+
+pub struct HalfEdge {
+    /// The vertex the half-edge starts at.
+    pub origin: usize,
+    /// The half-edge running the other way in the neighbouring tile, or
+    /// `None` on the boundary.
+    pub twin: Option<usize>,
+    pub next: usize,
+    pub prev: usize,
+    pub face: usize,
+    /// The tile edge this half-edge is, numbered from 1 as in `PenroseTile`.
+    pub edge: i32,
+}
+
+pub struct Face {
+    pub tile: Box<dyn PenroseTile>,
+    /// The half-edge for edge 1 of the tile; the rest follow by `next`.
+    pub half_edge: usize,
+    // What adding the tile did to the boundary, in order, to undo it.
+    changes: Vec<BoundaryChange>,
+}
+
+// One step in updating the boundary list.
+enum BoundaryChange {
+    // The half-edge was pushed on the end.
+    Opened(usize),
+    // The half-edge was taken out of `slot`, and the last one moved in.
+    Closed { half_edge: usize, slot: usize },
+}
+
+pub struct Mesh {
+    vertices: Vec<MeshVertex>,
+    half_edges: Vec<HalfEdge>,
+    faces: Vec<Face>,
+    // Vertices by position, to find the corners of new tiles.
+    vertex_index: SpatialHash<usize>,
+    // Faces by tile centre.
+    face_index: SpatialHash<usize>,
+    // The boundary half-edges, and where each half-edge is in the list.
+    boundary: Vec<usize>,
+    slots: Vec<Option<usize>>,
+    // Boundary half-edges by edge centre.
+    boundary_index: SpatialHash<usize>,
+    // Half-edges by their start and end vertices.
+    lookup: HashMap<(usize, usize), usize>,
+}
+
+impl Default for Mesh {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Mesh {
+            vertices: Vec::new(),
+            half_edges: Vec::new(),
+            faces: Vec::new(),
+            vertex_index: SpatialHash::new(VERTEX_TOLERANCE),
+            face_index: SpatialHash::new(4.),
+            boundary: Vec::new(),
+            slots: Vec::new(),
+            boundary_index: SpatialHash::new(4.),
+            lookup: HashMap::new(),
+        }
+    }
+
+    pub fn from_tiles(tiles: &[Box<dyn PenroseTile>]) -> Self {
+        let mut mesh = Mesh::new();
+        for t in tiles {
+            mesh.add_tile(t.as_ref());
+        }
+        mesh
+    }
+
+    pub fn vertices(&self) -> &[MeshVertex] {
+        &self.vertices
+    }
+
+    pub fn half_edges(&self) -> &[HalfEdge] {
+        &self.half_edges
+    }
+
+    pub fn faces(&self) -> &[Face] {
+        &self.faces
+    }
+
+    // The vertex at `p`, added if there isn't one. `half_edge` is only used
+    // for a new vertex.
+    fn vertex_at(&mut self, p: (f64, f64), exact: Option<ExactPoint>, half_edge: usize) -> usize {
+        if let Some(v) = self.vertex_index.near(p, VERTEX_TOLERANCE).first() {
+            return *v
+        }
+        let v = self.vertices.len();
+        self.vertices.push(MeshVertex { position: p, exact, half_edge });
+        self.vertex_index.insert(p, v);
+        v
+    }
+
+    /// Add a copy of `tile` as a new face, gluing it to the tiles it shares
+    /// edges with, and return the face index.
+    ///
+    /// An edge is only glued to one that runs the other way, so a tile laid
+    /// over another is added but stays unglued along the shared edges.
+    pub fn add_tile(&mut self, tile: &dyn PenroseTile) -> usize {
+        let face = self.faces.len();
+        let first = self.half_edges.len();
+        let points = tile.vertices();
+        let exact = tile.exact_vertices();
+        let n = points.len();
+
+        let corners: Vec<usize> = (0..n)
+            .map(|i| self.vertex_at(points[i], exact.as_ref().map(|e| e[i]), first + i))
+            .collect();
+
+        let edges = tile.get_edges();
+        let mut changes = Vec::new();
+        for i in 0..n {
+            let (a, b) = (corners[i], corners[(i+1)%n]);
+            let h = first + i;
+            let twin = self.lookup.get(&(b, a)).cloned()
+                .filter(|t| self.half_edges[*t].twin.is_none());
+            self.lookup.entry((a, b)).or_insert(h);
+            self.half_edges.push(HalfEdge {
+                origin: a,
+                twin,
+                next: first + (i+1)%n,
+                prev: first + (i+n-1)%n,
+                face,
+                edge: i as i32 + 1,
+            });
+            self.slots.push(None);
+            match twin {
+                Some(t) => {
+                    self.half_edges[t].twin = Some(h);
+                    changes.push(self.close(t));
+                }
+                None => changes.push(self.open(h, edges[i].center)),
+            }
+        }
+
+        self.face_index.insert(tile.center(), face);
+        self.faces.push(Face { tile: copy_tile(tile), half_edge: first, changes });
+        face
+    }
+
+    fn open(&mut self, h: usize, centre: (f64, f64)) -> BoundaryChange {
+        self.slots[h] = Some(self.boundary.len());
+        self.boundary.push(h);
+        self.boundary_index.insert(centre, h);
+        BoundaryChange::Opened(h)
+    }
+
+    fn close(&mut self, h: usize) -> BoundaryChange {
+        let slot = self.slots[h].unwrap();
+        self.boundary.swap_remove(slot);
+        if let Some(moved) = self.boundary.get(slot) {
+            self.slots[*moved] = Some(slot);
+        }
+        self.slots[h] = None;
+        let centre = self.edge(h).center;
+        self.boundary_index.remove(centre, h);
+        BoundaryChange::Closed { half_edge: h, slot }
+    }
+
+    // Undo `change`, which must be the last change made.
+    fn revert(&mut self, change: &BoundaryChange, centre: (f64, f64)) {
+        match change {
+            BoundaryChange::Opened(h) => {
+                self.boundary.pop();
+                self.slots[*h] = None;
+                self.boundary_index.remove(centre, *h);
+            }
+            BoundaryChange::Closed { half_edge, slot } => {
+                if let Some(moved) = self.boundary.get(*slot).cloned() {
+                    self.slots[moved] = Some(self.boundary.len());
+                    self.boundary.push(moved);
+                    self.boundary[*slot] = *half_edge;
+                } else {
+                    self.boundary.push(*half_edge);
+                }
+                self.slots[*half_edge] = Some(*slot);
+                self.boundary_index.insert(centre, *half_edge);
+            }
+        }
+    }
+
+    /// Remove the face added last, opening the edges it was glued along,
+    /// and return its tile. The boundary is left exactly as it was before
+    /// the tile was added, in the same order.
+    pub fn pop_tile(&mut self) -> Option<Box<dyn PenroseTile>> {
+        let face = self.faces.pop()?;
+        let first = face.half_edge;
+        self.face_index.remove(face.tile.center(), self.faces.len());
+
+        let edges = face.tile.get_edges();
+        for change in face.changes.iter().rev() {
+            match change {
+                BoundaryChange::Opened(h) => self.revert(change, edges[h - first].center),
+                BoundaryChange::Closed { half_edge, .. } => {
+                    self.half_edges[*half_edge].twin = None;
+                    let centre = self.edge(*half_edge).center;
+                    self.revert(change, centre);
+                }
+            }
+        }
+        for h in first..self.half_edges.len() {
+            let key = (self.half_edges[h].origin, self.destination(h));
+            if self.lookup.get(&key) == Some(&h) {
+                self.lookup.remove(&key);
+            }
+        }
+        self.half_edges.truncate(first);
+        self.slots.truncate(first);
+
+        // Faces are removed in the reverse order to how they were added, so
+        // the vertices this face brought are the last ones, and only it uses
+        // them.
+        while let Some(v) = self.vertices.last() {
+            if v.half_edge < first {
+                break
+            }
+            self.vertex_index.remove(v.position, self.vertices.len() - 1);
+            self.vertices.pop();
+        }
+        Some(face.tile)
+    }
+
+    /// The faces whose tile centres are within `r` of `p`.
+    pub fn faces_near(&self, p: (f64, f64), r: f64) -> Vec<usize> {
+        self.face_index.near(p, r)
+    }
+
+    /// The boundary half-edges whose centres are within `r` of `p`.
+    pub fn boundary_near(&self, p: (f64, f64), r: f64) -> Vec<usize> {
+        self.boundary_index.near(p, r)
+    }
+
+    /// The half-edge for edge `e` of `face`.
+    pub fn half_edge(&self, face: usize, e: i32) -> Option<usize> {
+        let f = self.faces.get(face)?;
+        let n = self.face_half_edges(face).len() as i32;
+        if e < 1 || e > n {
+            return None
+        }
+        Some(f.half_edge + (e - 1) as usize)
+    }
+
+    /// The half-edges around `face`, in edge order.
+    pub fn face_half_edges(&self, face: usize) -> Vec<usize> {
+        let start = self.faces[face].half_edge;
+        let mut result = vec![start];
+        let mut h = self.half_edges[start].next;
+        while h != start {
+            result.push(h);
+            h = self.half_edges[h].next;
+        }
+        result
+    }
+
+    /// The vertex a half-edge ends at.
+    pub fn destination(&self, h: usize) -> usize {
+        self.half_edges[self.half_edges[h].next].origin
+    }
+
+    /// The tile glued to edge `e` of `face`, and its edge number.
+    pub fn neighbour(&self, face: usize, e: i32) -> Option<(usize, i32)> {
+        let t = self.half_edges[self.half_edge(face, e)?].twin?;
+        Some((self.half_edges[t].face, self.half_edges[t].edge))
+    }
+
+    /// The half-edges with nothing glued to them. The list is kept up to
+    /// date as tiles are added and removed, rather than found each time.
+    pub fn boundary(&self) -> &[usize] {
+        &self.boundary
+    }
+
+    /// The boundary half-edge that follows `h` round the outside of the
+    /// patch: the first unglued half-edge leaving the vertex `h` ends at.
+    pub fn next_boundary(&self, h: usize) -> usize {
+        let mut g = self.half_edges[h].next;
+        while let Some(t) = self.half_edges[g].twin {
+            g = self.half_edges[t].next;
+        }
+        g
+    }
+
+    /// The boundary as closed loops of half-edges, one for the outside of
+    /// each piece of the patch and one for each hole.
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.half_edges.len()];
+        let mut result = Vec::new();
+        for start in self.boundary().iter().cloned() {
+            if seen[start] {
+                continue
+            }
+            let mut walk = Vec::new();
+            let mut h = start;
+            while !seen[h] {
+                seen[h] = true;
+                walk.push(h);
+                h = self.next_boundary(h);
+            }
+            result.push(walk);
+        }
+        result
+    }
+
+    /// The tile edges on the boundary, as `boundary_edges` gives them.
+    pub fn boundary_edges(&self) -> Vec<Edge> {
+        self.boundary().iter().map(|h| self.edge(*h)).collect()
+    }
+
+    /// The tile edge a half-edge stands for.
+    pub fn edge(&self, h: usize) -> Edge {
+        let he = &self.half_edges[h];
+        self.faces[he.face].tile.get_edges()[(he.edge - 1) as usize].clone()
+    }
+
+    /// The tiles around vertex `v` as (face, corner) pairs, in order round
+    /// the vertex. If the vertex is on the boundary they run from one
+    /// boundary edge to the other.
+    pub fn vertex_star(&self, v: usize) -> Vec<(usize, usize)> {
+        let corner = |h: usize| {
+            let he = &self.half_edges[h];
+            (he.face, (he.edge - 1) as usize)
+        };
+        // Half-edges leaving v, turning one way: from h to the twin of the
+        // half-edge before it.
+        let start = self.vertices[v].half_edge;
+        let mut result = vec![corner(start)];
+        let mut h = start;
+        loop {
+            match self.half_edges[self.half_edges[h].prev].twin {
+                Some(t) if t == start => return result,
+                Some(t) => {
+                    result.push(corner(t));
+                    h = t;
+                }
+                None => break,
+            }
+        }
+
+        // Hit the boundary, so pick up the rest turning the other way.
+        let mut before = Vec::new();
+        h = start;
+        while let Some(t) = self.half_edges[h].twin {
+            h = self.half_edges[t].next;
+            before.push(corner(h));
+        }
+        before.reverse();
+        before.extend(result);
+        before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::*;
+    use crate::deflate::tests::*;
+    use crate::deflate::*;
+    use crate::rules::*;
+    use crate::vertex::*;
+
+    fn same_edges(a: &[Edge], b: &[Edge]) -> bool {
+        let key = |e: &Edge| ((e.center.0 * 1e6).round() as i64, (e.center.1 * 1e6).round() as i64, e.angle);
+        let mut ka: Vec<_> = a.iter().map(key).collect();
+        let mut kb: Vec<_> = b.iter().map(key).collect();
+        ka.sort();
+        kb.sort();
+        ka == kb
+    }
+
+    #[test]
+    fn test_mesh_gluing() {
+        for tiles in [deflate_n(&sun(), 3), to_triangles(&deflate_n(&sun(), 2)),
+                      deflate_n(&[new_exact_tile(Tile::FAT, ExactPoint::origin(), 0)], 3)].iter() {
+            let mesh = Mesh::from_tiles(tiles);
+            assert_eq!(mesh.faces().len(), tiles.len());
+            assert!(same_edges(&mesh.boundary_edges(), &boundary_edges(tiles)));
+
+            // Neighbours are mutual and really share the edge.
+            for (f, face) in mesh.faces().iter().enumerate() {
+                for e in 1..=face.tile.vertices().len() as i32 {
+                    if let Some((g, d)) = mesh.neighbour(f, e) {
+                        assert_eq!(mesh.neighbour(g, d), Some((f, e)));
+                        let a = face.tile.get_edges()[(e - 1) as usize].clone();
+                        let b = mesh.faces()[g].tile.get_edges()[(d - 1) as usize].clone();
+                        assert!((a.center.0 - b.center.0).hypot(a.center.1 - b.center.1) < 1e-9);
+                        assert_eq!((a.angle + 180) % 360, b.angle);
+                    }
+                }
+            }
+
+            // Each vertex is found once.
+            let v = mesh.vertices();
+            for i in 0..v.len() {
+                for j in i+1..v.len() {
+                    let (p, q) = (v[i].position, v[j].position);
+                    assert!((p.0 - q.0).hypot(p.1 - q.1) > 0.1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mesh_boundary() {
+        // A deflated sun is a disc, so its boundary is one loop, and each
+        // step round it starts where the last one ended.
+        let mesh = Mesh::from_tiles(&deflate_n(&sun(), 2));
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), mesh.boundary().len());
+        for (i, h) in loops[0].iter().enumerate() {
+            let next = loops[0][(i + 1) % loops[0].len()];
+            assert_eq!(mesh.destination(*h), mesh.half_edges()[next].origin);
+        }
+
+        // Two separate tiles have a loop each.
+        let apart = [new_tile(Tile::KITE, 0., 0., 0), new_tile(Tile::KITE, 10., 0., 0)];
+        assert_eq!(Mesh::from_tiles(&apart).boundary_loops().len(), 2);
+    }
+
+    #[test]
+    fn test_mesh_pop() {
+        // Popping tiles goes back through the same meshes as adding them,
+        // with the boundary in the same order.
+        let tiles = deflate_n(&sun(), 3);
+        let mut mesh = Mesh::new();
+        let mut states = Vec::new();
+        for t in tiles.iter() {
+            states.push((mesh.vertices().len(), mesh.boundary().to_vec()));
+            mesh.add_tile(t.as_ref());
+
+            // The boundary kept is the one there is.
+            let mut boundary = mesh.boundary().to_vec();
+            boundary.sort();
+            let open: Vec<usize> = (0..mesh.half_edges().len())
+                .filter(|h| mesh.half_edges()[*h].twin.is_none())
+                .collect();
+            assert_eq!(boundary, open);
+        }
+        while let Some((vertices, boundary)) = states.pop() {
+            let t = mesh.pop_tile().unwrap();
+            assert_eq!(t.center(), tiles[states.len()].center());
+            assert_eq!(mesh.vertices().len(), vertices);
+            assert_eq!(mesh.boundary(), &boundary[..]);
+            assert_eq!(mesh.faces().len(), states.len());
+        }
+        assert!(mesh.pop_tile().is_none());
+        assert!(mesh.boundary_near((0., 0.), 100.).is_empty());
+        assert!(mesh.faces_near((0., 0.), 100.).is_empty());
+    }
+
+    #[test]
+    fn test_mesh_near() {
+        let tiles = deflate_n(&sun(), 4);
+        let mut mesh = Mesh::from_tiles(&tiles);
+        for _ in 0..20 {
+            mesh.pop_tile();
+        }
+        for p in [(0., 0.), (3., -2.), (-6., 5.5)].iter() {
+            let mut near = mesh.boundary_near(*p, 3.);
+            near.sort();
+            let mut expected: Vec<usize> = mesh.boundary().iter().cloned()
+                .filter(|h| { let c = mesh.edge(*h).center; (c.0 - p.0).hypot(c.1 - p.1) < 3. })
+                .collect();
+            expected.sort();
+            assert_eq!(near, expected);
+
+            let mut near = mesh.faces_near(*p, 3.);
+            near.sort();
+            let expected: Vec<usize> = (0..mesh.faces().len())
+                .filter(|f| { let c = mesh.faces()[*f].tile.center(); (c.0 - p.0).hypot(c.1 - p.1) < 3. })
+                .collect();
+            assert_eq!(near, expected);
+        }
+    }
+
+    #[test]
+    fn test_vertex_star() {
+        for v in VertexType::all().iter() {
+            let tiles = v.tiles((0., 0.), 0).unwrap();
+            let mesh = Mesh::from_tiles(&tiles);
+            let centre = (0..mesh.vertices().len())
+                .find(|i| { let p = mesh.vertices()[*i].position; p.0.hypot(p.1) < 1e-9 })
+                .unwrap();
+
+            // Every tile is round the vertex once, at the corner that is
+            // there, and they fill it.
+            let star = mesh.vertex_star(centre);
+            assert_eq!(star.len(), tiles.len());
+            let mut angle = 0;
+            for (f, c) in star.iter() {
+                let p = mesh.faces()[*f].tile.vertices()[*c];
+                assert!(p.0.hypot(p.1) < 1e-9);
+                angle += mesh.faces()[*f].tile.interior_angles()[*c];
+            }
+            assert_eq!(angle, 360);
+
+            // A corner on the outside has a partial star of consecutive
+            // neighbours.
+            let outer = mesh.destination(mesh.boundary()[0]);
+            let star = mesh.vertex_star(outer);
+            for w in star.windows(2) {
+                let (f, c) = w[0];
+                let n = mesh.faces()[f].tile.vertices().len() as i32;
+                let prev = (c as i32 + n - 1) % n + 1;
+                assert_eq!(mesh.neighbour(f, prev).map(|(g, _)| g), Some(w[1].0));
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! A region of tiles moved about as one piece.
+//!
+//! The tiles keep their own frame, where they meet exactly, and the patch
+//! records where that frame has been put: turned about any point, shifted,
+//! scaled and mirrored. Every tile moves the same way, so tiles that shared
+//! an edge still do, and the tiles themselves can be matched against as
+//! before.
+
+use crate::tiles::*;
+use crate::transform::*;
+
+pub struct Patch {
+    tiles: Vec<Box<dyn PenroseTile>>,
+    placement: RigidTransform,
+    factor: f64,
+}
+
+impl Patch {
+    /// A patch of `tiles` where they are.
+    pub fn new(tiles: Vec<Box<dyn PenroseTile>>) -> Self {
+        Patch { tiles, placement: RigidTransform::identity(), factor: 1. }
+    }
+
+    /// The tiles in the patch's own frame.
+    pub fn tiles(&self) -> &[Box<dyn PenroseTile>] {
+        &self.tiles
+    }
+
+    pub fn into_tiles(self) -> Vec<Box<dyn PenroseTile>> {
+        self.tiles
+    }
+
+    /// How the tiles are turned, mirrored and shifted, before scaling.
+    /// Export with this and the scale multiplied by `scale_factor()`.
+    pub fn placement(&self) -> &RigidTransform {
+        &self.placement
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.factor
+    }
+
+    /// Where `p` in the patch's frame has been moved to.
+    pub fn apply(&self, p: (f64, f64)) -> (f64, f64) {
+        let q = self.placement.apply(p);
+        (self.factor * q.0, self.factor * q.1)
+    }
+
+    /// Where the vertices of each tile have been moved to.
+    pub fn vertices(&self) -> Vec<Vec<(f64, f64)>> {
+        self.tiles.iter().map(|t| t.vertices().iter().map(|p| self.apply(*p)).collect()).collect()
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        BoundingBox::around_points(self.tiles.iter().flat_map(|t| t.vertices()).map(|p| self.apply(p)))
+    }
+
+    // Moves the patch by `t`, given in the plane at the patch's scale.
+    fn then(&mut self, t: &RigidTransform) {
+        self.placement = self.placement.then(t);
+    }
+
+    // A point in the plane, brought back to before scaling.
+    fn unscaled(&self, p: (f64, f64)) -> (f64, f64) {
+        (p.0 / self.factor, p.1 / self.factor)
+    }
+
+    /// Turn the patch `degrees` anticlockwise about `p`.
+    pub fn rotate_about(&mut self, p: (f64, f64), degrees: f64) {
+        let t = RigidTransform::rotation_about(self.unscaled(p), degrees);
+        self.then(&t);
+    }
+
+    pub fn translate(&mut self, dx: f64, dy: f64) {
+        let t = RigidTransform::translation(dx / self.factor, dy / self.factor);
+        self.then(&t);
+    }
+
+    /// Scale the patch by `factor` about the origin.
+    pub fn scale(&mut self, factor: f64) {
+        assert!(factor > 0. && factor.is_finite(), "bad scale factor {}", factor);
+        self.factor *= factor;
+    }
+
+    /// Mirror the patch in the line through `p` at `degrees`.
+    pub fn reflect(&mut self, p: (f64, f64), degrees: f64) {
+        let t = RigidTransform::reflection_about(self.unscaled(p), degrees);
+        self.then(&t);
+    }
+
+    /// The tiles rebuilt where the patch has put them, so they can join
+    /// another patch. Only turns by multiples of 36 degrees, mirrors in lines
+    /// at multiples of 18 degrees and shifts keep tiles that the tile structs
+    /// can hold; otherwise `None`.
+    pub fn placed_tiles(&self) -> Option<Vec<Box<dyn PenroseTile>>> {
+        let steps = self.placement.rotation / 36.;
+        if (steps - steps.round()).abs() > 1e-9 || (self.factor - 1.).abs() > 1e-9 {
+            return None
+        }
+        let turn = Orientation::new(steps.round() as i32).degrees();
+        let mirror = self.placement.reflected;
+        Some(self.tiles.iter().map(|t| {
+            // A mirror turns the tile the other way, and over.
+            let c = self.apply(t.center());
+            let angle = if mirror { turn - t.angle() } else { turn + t.angle() };
+            let placed = new_tile(t.kind(), c.0, c.1, angle);
+            if t.reflected() != mirror { reflect_tile(placed.as_ref()) } else { placed }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vertex::*;
+
+    fn close(p: (f64, f64), q: (f64, f64)) -> bool {
+        (p.0 - q.0).hypot(p.1 - q.1) < 1e-9
+    }
+
+    fn star() -> Patch {
+        Patch::new(VertexType::Star.tiles((0., 0.), 0).unwrap())
+    }
+
+    // Twice the signed area of a polygon, positive when anticlockwise.
+    fn area(pts: &[(f64, f64)]) -> f64 {
+        (0..pts.len()).map(|i| {
+            let (p, q) = (pts[i], pts[(i + 1) % pts.len()]);
+            p.0 * q.1 - q.0 * p.1
+        }).sum()
+    }
+
+    #[test]
+    fn test_patch_moves() {
+        let mut patch = star();
+        let before = patch.vertices();
+        patch.rotate_about((1., 2.), 23.);
+        patch.translate(-3., 0.5);
+        patch.scale(2.);
+        patch.reflect((0.5, -1.), 70.);
+        let after = patch.vertices();
+
+        // Every distance doubles, so tiles that met still meet.
+        let all = |v: &Vec<Vec<(f64, f64)>>| v.iter().flatten().cloned().collect::<Vec<_>>();
+        let (b, a) = (all(&before), all(&after));
+        let d = |p: (f64, f64), q: (f64, f64)| (p.0 - q.0).hypot(p.1 - q.1);
+        for i in 0..b.len() {
+            for j in 0..b.len() {
+                assert!((d(a[i], a[j]) - 2. * d(b[i], b[j])).abs() < 1e-9);
+            }
+        }
+
+        // The mirror turns each tile over.
+        for (p, q) in before.iter().zip(after.iter()) {
+            assert!(area(p) > 0. && area(q) < 0.);
+        }
+    }
+
+    #[test]
+    fn test_patch_pivots() {
+        let mut patch = star();
+        patch.scale(3.);
+        patch.translate(1., 1.);
+        let p = patch.vertices()[0][1];
+        patch.rotate_about(p, 50.);
+        assert!(close(patch.vertices()[0][1], p));
+        patch.reflect(p, -10.);
+        assert!(close(patch.vertices()[0][1], p));
+
+        // Mirroring twice in the same line puts it back.
+        let before = patch.vertices();
+        patch.reflect((2., 0.), 33.);
+        patch.reflect((2., 0.), 33.);
+        for (p, q) in before.iter().flatten().zip(patch.vertices().iter().flatten()) {
+            assert!(close(*p, *q));
+        }
+        assert!(patch.bounds().is_some());
+        assert!(Patch::new(Vec::new()).bounds().is_none());
+    }
+
+    #[test]
+    fn test_placed_tiles() {
+        // The same vertices, in any order since a mirror reverses them, and
+        // the same Ammann bars.
+        let check = |patch: &Patch| {
+            let placed = patch.placed_tiles().unwrap();
+            for ((t, v), old) in placed.iter().zip(patch.vertices()).zip(patch.tiles()) {
+                assert!(t.vertices().iter().all(|p| v.iter().any(|q| close(*p, *q))));
+                let bars: Vec<_> = old.get_ammann_bars().iter().map(|p| patch.apply(*p)).collect();
+                assert!(t.get_ammann_bars().iter().all(|p| bars.iter().any(|q| close(*p, *q))));
+            }
+            placed
+        };
+        let mut patch = star();
+        patch.rotate_about((0.3, -2.), -108.);
+        patch.translate(4., 1.);
+        assert!(check(&patch).iter().all(|t| !t.reflected()));
+
+        // Skinny rhombi show whether the mirror turned the tiles over.
+        let mut rhombi = Patch::new(vec![new_tile(Tile::SKINNY, 0., 0., 0), new_tile(Tile::FAT, 2., 1., 144)]);
+        rhombi.reflect((1., -1.), 54.);
+        let placed = check(&rhombi);
+        assert!(placed.iter().all(|t| t.reflected()));
+        rhombi.reflect((0., 3.), -18.);
+        assert!(check(&rhombi).iter().all(|t| !t.reflected()));
+        let mut triangles = Patch::new(vec![new_tile(Tile::ROBINSON_B, 1., 0., 36)]);
+        triangles.reflect((0., 0.), 90.);
+        assert_eq!(check(&triangles)[0].kind(), Tile::ROBINSON_B_MIRROR);
+
+        // Tiles can't be turned by other angles or scaled.
+        patch.rotate_about((0., 0.), 10.);
+        assert!(patch.placed_tiles().is_none());
+        let mut patch = star();
+        patch.reflect((0., 0.), 9.);
+        assert!(patch.placed_tiles().is_none());
+        let mut patch = star();
+        patch.scale(2.);
+        assert!(patch.placed_tiles().is_none());
+    }
+}
@@ -0,0 +1,179 @@
+//! Rhombus tilings from de Bruijn's pentagrid.
+//!
+//! The pentagrid is five families of parallel lines, family `j` being the
+//! points `x` with `x·e_j + γ_j` an integer, where `e_j` is the unit vector
+//! at 72j degrees. Every crossing of a line from family `j` with one from
+//! family `k` becomes a rhombus with edges along `e_j` and `e_k`, and every
+//! region between the lines becomes a tiling vertex
+//! `K_0 e_0 + ... + K_4 e_4`, where `K_i` is `x·e_i + γ_i` rounded up.
+//!
+//! Crossings of neighbouring families (72 or 288 degrees apart) give fat
+//! rhombi and the rest give skinny ones. When the offsets sum to an integer
+//! the tiling obeys the Penrose matching rules, and the arcs are placed using
+//! the index `K_0 + ... + K_4` of each vertex, which is then 1 to 4 up to
+//! that integer.
+//!
+//! The tiling vertices are in Z[ζ], so the rhombi are placed exactly, at the
+//! usual tile size of phi.
+
+use crate::exact::{Cyclotomic, ExactPoint};
+use crate::tiles::*;
+
+pub(crate) fn grid_vector(j: usize) -> (f64, f64) {
+    let a = (72 * j) as f64 * std::f64::consts::PI / 180.;
+    (a.cos(), a.sin())
+}
+
+fn dot(p: (f64, f64), q: (f64, f64)) -> f64 {
+    p.0*q.0 + p.1*q.1
+}
+
+// The tiling vertex of the region with coordinates `k`.
+pub(crate) fn vertex(k: &[i64; 5]) -> ExactPoint {
+    let z = (0..5).fold(Cyclotomic::ZERO, |z, i| {
+        z + Cyclotomic::new(k[i], 0, 0, 0) * Cyclotomic::zeta_pow(2 * i as i32)
+    });
+    ExactPoint::new(z * Cyclotomic::phi())
+}
+
+fn direction(p: ExactPoint, q: ExactPoint) -> i32 {
+    let (x, y) = (q - p).to_f64();
+    let a = y.atan2(x).to_degrees();
+    ((a / 36.).round() as i32 * 36).rem_euclid(360)
+}
+
+// The rhombus with corners `base`, `base + u_j`, `base + u_k` and
+// `base + u_j + u_k` in Z^5, given the integer the offsets sum to. At the
+// crossing of line `n` of family `j` with line `m` of family `k`, `base` is
+// the region on the low side of both lines, with `base[j] = n`,
+// `base[k] = m`.
+pub(crate) fn rhombus(j: usize, k: usize, base: &[i64; 5], sum: i64) -> Box<dyn PenroseTile> {
+    let mut corner = *base;
+    let v0 = vertex(&corner);
+    corner[j] += 1;
+    corner[k] += 1;
+    let v2 = vertex(&corner);
+    let center = v0.midpoint(&v2);
+
+    // v0 and v2 carry the arcs: the acute vertices of a fat rhombus and the
+    // obtuse vertices of a skinny one. Their indices are 1 and 3 or 2 and 4,
+    // and the big arc goes at the vertex of index 2 or 3.
+    let index = base.iter().sum::<i64>() - sum;
+    let (big, small) = if index == 2 { (v0, v2) } else { (v2, v0) };
+    match k - j {
+        1 | 4 => new_exact_tile(Tile::FAT, center, direction(big, small)),
+        _ => new_exact_tile(Tile::SKINNY, center, (direction(small, big) + 72) % 360),
+    }
+}
+
+/// The rhombus tiling dual to the pentagrid with offsets `gamma`, keeping
+/// the tiles whose centre lies in `region`.
+///
+/// The offsets must be regular, meaning no three grid lines meet at a point.
+/// The symmetric tilings come from singular offsets such as all zeros; use
+/// offsets a small generic step away from them instead. For a Penrose tiling
+/// the offsets should sum to an integer, usually 0.
+pub fn pentagrid(gamma: [f64; 5], region: &BoundingBox) -> Vec<Box<dyn PenroseTile>> {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+    let sum = gamma.iter().sum::<f64>().round() as i64;
+    let e: Vec<(f64, f64)> = (0..5).map(grid_vector).collect();
+
+    // A point x of the grid maps to within a few edge lengths of
+    // phi * (5x/2 + sum of gamma_i e_i), so search the matching part of the
+    // grid with some margin and then filter by tile centre.
+    let shift = (0..5).fold((0., 0.), |s, i| (s.0 + gamma[i]*e[i].0, s.1 + gamma[i]*e[i].1));
+    let to_grid = |p: (f64, f64)| (0.4 * (p.0/phi - shift.0), 0.4 * (p.1/phi - shift.1));
+    let margin = 3.;
+    let lo = to_grid(region.min);
+    let hi = to_grid(region.max);
+    let corners = [(lo.0 - margin, lo.1 - margin), (hi.0 + margin, lo.1 - margin),
+                   (lo.0 - margin, hi.1 + margin), (hi.0 + margin, hi.1 + margin)];
+    let lines = |i: usize| {
+        let values: Vec<f64> = corners.iter().map(|c| dot(*c, e[i]) + gamma[i]).collect();
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        min.ceil() as i64..=max.floor() as i64
+    };
+
+    let mut result = Vec::new();
+    for j in 0..5 {
+        for k in j+1..5 {
+            let det = e[j].0*e[k].1 - e[j].1*e[k].0;
+            for nj in lines(j) {
+                for nk in lines(k) {
+                    // Solve x.e_j = nj - gamma_j, x.e_k = nk - gamma_k.
+                    let (bj, bk) = (nj as f64 - gamma[j], nk as f64 - gamma[k]);
+                    let x = ((bj*e[k].1 - bk*e[j].1) / det, (bk*e[j].0 - bj*e[k].0) / det);
+
+                    let mut base = [0; 5];
+                    for i in 0..5 {
+                        base[i] = (dot(x, e[i]) + gamma[i]).ceil() as i64;
+                    }
+                    base[j] = nj;
+                    base[k] = nk;
+
+                    let tile = rhombus(j, k, &base, sum);
+                    if region.contains(tile.center()) {
+                        result.push(tile);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+    use crate::inflate::*;
+
+    const GAMMA: [f64; 5] = [0.1, 0.37, -0.24, 0.02, -0.25];
+
+    #[test]
+    fn test_pentagrid() {
+        let region = BoundingBox::around((0., 0.), 30.);
+        let tiles = pentagrid(GAMMA, &region);
+        assert!(tiles.iter().all(|t| region.contains(t.center()) && t.exact_center().is_some()));
+        check_no_overlaps(&tiles);
+        check_rhombus_arcs(&tiles);
+
+        // Fat rhombi outnumber skinny ones by about phi.
+        let ratio = count(&tiles, Tile::FAT) as f64 / count(&tiles, Tile::SKINNY) as f64;
+        assert!((ratio - 1.618).abs() < 0.05);
+
+        // The region is covered apart from its border.
+        let area: f64 = tiles.iter().map(|t| match t.kind() {
+            Tile::FAT => 2.4899,  // phi^2 sin 72
+            _ => 1.5388,          // phi^2 sin 36
+        }).sum();
+        assert!(area > 0.9 * 60. * 60.);
+    }
+
+    #[test]
+    fn test_pentagrid_composes() {
+        // A legal Penrose tiling composes, apart from near the border.
+        let tiles = pentagrid(GAMMA, &BoundingBox::around((0., 0.), 15.));
+        let inflation = inflate(&tiles);
+        assert!(!inflation.tiles.is_empty());
+        assert!(inflation.uncomposed.len() * 2 < tiles.len());
+    }
+
+    #[test]
+    fn test_pentagrid_anywhere() {
+        // A patch far from the origin is cut from the same tiling.
+        let region = BoundingBox::new((1000., -500.), (1010., -490.));
+        let tiles = pentagrid(GAMMA, &region);
+        assert!(tiles.len() > 20);
+        check_no_overlaps(&tiles);
+        check_rhombus_arcs(&tiles);
+
+        let bigger = pentagrid(GAMMA, &BoundingBox::new((995., -505.), (1015., -485.)));
+        for t in tiles.iter() {
+            assert!(bigger.iter().any(|u| u.kind() == t.kind() && u.angle() == t.angle()
+                                      && u.exact_center() == t.exact_center()));
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Rhombus tilings by cut and project from the Z^5 lattice.
+//!
+//! R^5 splits into the tiling plane, spanned by the rows `e_i` at 72i
+//! degrees, the perpendicular plane, spanned by the rows `ε_i` at 144i
+//! degrees, and the diagonal. A lattice point `n` is kept if its lift, the
+//! perpendicular coordinate `Σ (n_i - γ_i) ε_i` together with the index
+//! `Σ n_i`, lies in the acceptance window, and is placed at `phi Σ n_i e_i`.
+//! Neighbouring kept points then give the rhombi.
+//!
+//! The canonical window is the projection of the unit 5-cube: for each index
+//! a pentagon, and with the same offsets it gives exactly the tiling from
+//! `pentagrid`. Other windows give other point sets, which need not tile.
+
+use std::collections::HashMap;
+
+use crate::exact::ExactPoint;
+use crate::pentagrid::*;
+use crate::tiles::*;
+
+/// A lattice point and its coordinates in the tiling and in perpendicular
+/// space.
+#[derive(Clone, Debug)]
+pub struct LatticeVertex {
+    pub lattice: [i64; 5],
+    /// The vertex in the tiling, in the usual tile coordinates.
+    pub position: ExactPoint,
+    /// The perpendicular coordinate, relative to the offsets.
+    pub perp: (f64, f64),
+    /// The sum of the lattice coordinates.
+    pub index: i64,
+}
+
+/// Which lattice points are kept.
+pub enum Window {
+    /// The projection of the unit 5-cube, which gives a Penrose tiling when
+    /// the offsets sum to an integer.
+    Canonical,
+    /// The canonical window scaled about its centre.
+    Scaled(f64),
+    /// Keep the points for which `accept(perp, index)` holds. Every kept
+    /// point must have a perpendicular coordinate within `radius`, and an
+    /// index within 10 of the sum of the offsets.
+    Custom { radius: f64, accept: Box<dyn Fn((f64, f64), i64) -> bool> },
+}
+
+/// The result of cut and project.
+pub struct Projection {
+    pub tiles: Vec<Box<dyn PenroseTile>>,
+    /// Every kept lattice point within reach of the region.
+    pub vertices: Vec<LatticeVertex>,
+    /// For each tile, the indices in `vertices` of its corners `n`,
+    /// `n + u_j`, `n + u_j + u_k` and `n + u_k`.
+    pub corners: Vec<[usize; 4]>,
+}
+
+fn perp_vector(i: usize) -> (f64, f64) {
+    grid_vector(2 * i % 5)
+}
+
+// The largest value of u·p over the canonical window at `level`, the index
+// less the sum of the offsets: put as much of `level` as possible on the
+// coordinates whose perpendicular vectors point furthest along `u`.
+fn support(u: (f64, f64), level: f64) -> f64 {
+    let mut d: Vec<f64> = (0..5).map(|i| {
+        let e = perp_vector(i);
+        u.0*e.0 + u.1*e.1
+    }).collect();
+    d.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    let mut left = level;
+    let mut h = 0.;
+    for x in d {
+        let c = left.min(1.);
+        h += c * x;
+        left -= c;
+    }
+    h
+}
+
+fn in_canonical(perp: (f64, f64), level: f64, scale: f64) -> bool {
+    if level <= 0. || level >= 5. {
+        return false
+    }
+    // The window is a polygon with edges along the differences of the
+    // perpendicular vectors, so its edge normals are at multiples of 36.
+    (0..10).all(|k| {
+        let a = (36 * k) as f64 * std::f64::consts::PI / 180.;
+        let u = (a.cos(), a.sin());
+        u.0*perp.0 + u.1*perp.1 < scale * support(u, level)
+    })
+}
+
+impl Window {
+    fn radius(&self) -> f64 {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        match self {
+            Window::Canonical => phi,
+            Window::Scaled(s) => phi * s,
+            Window::Custom { radius, .. } => *radius,
+        }
+    }
+
+    fn accepts(&self, v: &LatticeVertex, gamma_sum: f64) -> bool {
+        match self {
+            Window::Canonical => in_canonical(v.perp, v.index as f64 - gamma_sum, 1.),
+            Window::Scaled(s) => in_canonical(v.perp, v.index as f64 - gamma_sum, *s),
+            Window::Custom { accept, .. } => accept(v.perp, v.index),
+        }
+    }
+}
+
+fn lift(n: [i64; 5], gamma: &[f64; 5]) -> LatticeVertex {
+    let perp = (0..5).fold((0., 0.), |p, i| {
+        let e = perp_vector(i);
+        let c = n[i] as f64 - gamma[i];
+        (p.0 + c*e.0, p.1 + c*e.1)
+    });
+    LatticeVertex { lattice: n, position: vertex(&n), perp, index: n.iter().sum() }
+}
+
+/// The tiling from the lattice points whose lift lies in `window`, keeping
+/// the tiles whose centre lies in `region`.
+///
+/// Only the lattice points that project near `region` are visited, so a
+/// patch can be made around any point of the plane.
+pub fn cut_and_project(gamma: [f64; 5], region: &BoundingBox, window: &Window) -> Projection {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+    let gamma_sum: f64 = gamma.iter().sum();
+    let sum = gamma_sum.round() as i64;
+    let radius = window.radius();
+
+    // n_i = 2/5 (P·e_i + Q·ε_i) + index/5, where P = Σ n_j e_j is the
+    // position over phi and Q = Σ n_j ε_j, so bound each coordinate using
+    // the region, grown by one edge, and the window.
+    let corners = [(region.min.0 - phi, region.min.1 - phi), (region.max.0 + phi, region.min.1 - phi),
+                   (region.min.0 - phi, region.max.1 + phi), (region.max.0 + phi, region.max.1 + phi)];
+    let gamma_perp = (0..5).fold((0., 0.), |p, i| {
+        let e = perp_vector(i);
+        (p.0 + gamma[i]*e.0, p.1 + gamma[i]*e.1)
+    });
+    let indices = match window {
+        Window::Custom { .. } => sum - 10..=sum + 10,
+        _ => gamma_sum.floor() as i64 + 1..=gamma_sum.ceil() as i64 + 4,
+    };
+    let bounds = |i: usize, index: i64| {
+        let e = grid_vector(i);
+        let f = perp_vector(i);
+        let along: Vec<f64> = corners.iter().map(|c| (c.0*e.0 + c.1*e.1) / phi).collect();
+        let min = along.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = along.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let q = gamma_perp.0*f.0 + gamma_perp.1*f.1;
+        let lo = 0.4 * (min + q - radius) + index as f64 / 5.;
+        let hi = 0.4 * (max + q + radius) + index as f64 / 5.;
+        (lo.floor() as i64, hi.ceil() as i64)
+    };
+
+    let mut vertices = Vec::new();
+    let mut lookup = HashMap::new();
+    for index in indices {
+        let b: Vec<(i64, i64)> = (0..5).map(|i| bounds(i, index)).collect();
+        for n0 in b[0].0..=b[0].1 {
+            for n1 in b[1].0..=b[1].1 {
+                for n2 in b[2].0..=b[2].1 {
+                    for n3 in b[3].0..=b[3].1 {
+                        let n4 = index - n0 - n1 - n2 - n3;
+                        if n4 < b[4].0 || n4 > b[4].1 {
+                            continue
+                        }
+                        let v = lift([n0, n1, n2, n3, n4], &gamma);
+                        let (x, y) = v.position.to_f64();
+                        if x < corners[0].0 || x > corners[3].0 || y < corners[0].1 || y > corners[3].1 {
+                            continue
+                        }
+                        if window.accepts(&v, gamma_sum) {
+                            lookup.insert(v.lattice, vertices.len());
+                            vertices.push(v);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut tiles = Vec::new();
+    let mut tile_corners = Vec::new();
+    for (i, v) in vertices.iter().enumerate() {
+        for j in 0..5 {
+            for k in j+1..5 {
+                let step = |dj: i64, dk: i64| {
+                    let mut n = v.lattice;
+                    n[j] += dj;
+                    n[k] += dk;
+                    lookup.get(&n).cloned()
+                };
+                if let (Some(a), Some(b), Some(c)) = (step(1, 0), step(1, 1), step(0, 1)) {
+                    let tile = rhombus(j, k, &v.lattice, sum);
+                    if region.contains(tile.center()) {
+                        tiles.push(tile);
+                        tile_corners.push([i, a, b, c]);
+                    }
+                }
+            }
+        }
+    }
+
+    Projection { tiles, vertices, corners: tile_corners }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+    use std::collections::HashSet;
+
+    const GAMMA: [f64; 5] = [0.1, 0.37, -0.24, 0.02, -0.25];
+
+    fn keys(tiles: &[Box<dyn PenroseTile>]) -> HashSet<(Tile, i32, Option<ExactPoint>)> {
+        tiles.iter().map(|t| (t.kind(), t.angle(), t.exact_center())).collect()
+    }
+
+    #[test]
+    fn test_matches_pentagrid() {
+        for (gamma, region) in [(GAMMA, BoundingBox::around((0., 0.), 10.)),
+                                ([0.3, -0.1, 0.05, 0.45, 0.3], BoundingBox::around((-200., 340.), 8.))].iter() {
+            let projection = cut_and_project(*gamma, region, &Window::Canonical);
+            assert!(!projection.tiles.is_empty());
+            assert_eq!(keys(&projection.tiles), keys(&pentagrid(*gamma, region)));
+            check_rhombus_arcs(&projection.tiles);
+        }
+    }
+
+    #[test]
+    fn test_lift() {
+        let projection = cut_and_project(GAMMA, &BoundingBox::around((0., 0.), 8.), &Window::Canonical);
+        for v in projection.vertices.iter() {
+            assert!(v.index >= 1 && v.index <= 4);
+            assert!(v.perp.0.hypot(v.perp.1) < 1.62);
+            assert_eq!(v.position, vertex(&v.lattice));
+        }
+        // The corners of each tile are its vertices.
+        for (t, c) in projection.tiles.iter().zip(projection.corners.iter()) {
+            let vertices = t.exact_vertices().unwrap();
+            for i in c.iter() {
+                assert!(vertices.contains(&projection.vertices[*i].position));
+            }
+        }
+    }
+
+    #[test]
+    fn test_windows() {
+        let region = BoundingBox::around((0., 0.), 8.);
+        let canonical = cut_and_project(GAMMA, &region, &Window::Canonical);
+
+        // A smaller window keeps fewer points, and only those of the
+        // canonical window.
+        let smaller = cut_and_project(GAMMA, &region, &Window::Scaled(0.8));
+        assert!(smaller.vertices.len() < canonical.vertices.len());
+        let kept: HashSet<[i64; 5]> = canonical.vertices.iter().map(|v| v.lattice).collect();
+        assert!(smaller.vertices.iter().all(|v| kept.contains(&v.lattice)));
+
+        // A custom window that keeps the canonical points of index 1 and 2.
+        let custom = Window::Custom {
+            radius: 2.,
+            accept: Box::new(|p, index| (index == 1 || index == 2) && in_canonical(p, index as f64, 1.)),
+        };
+        let low = cut_and_project(GAMMA, &region, &custom);
+        assert_eq!(low.vertices.len(), canonical.vertices.iter().filter(|v| v.index <= 2).count());
+    }
+}
@@ -0,0 +1,367 @@
+//! PNG export through a small software renderer.
+//!
+//! A patch is drawn the way `view()` draws it: placed by a `RigidTransform`,
+//! with the origin in the middle of the image, y up, `scale` pixels per unit,
+//! and each tile filled, edged and then decorated with its arcs and bars
+//! before the next tile is drawn.
+//! Shapes are anti-aliased by sampling each pixel on a 4 by 4 grid.
+//!
+//! The PNG is written without compression, as zlib stored blocks, so it
+//! needs nothing outside the standard library.
+
+use std::path::Path;
+
+use crate::style::*;
+use crate::tiles::*;
+use crate::transform::*;
+
+pub type Rgba = [u8; 4];
+
+// Samples per pixel along each axis.
+const SAMPLES: usize = 4;
+
+// From the middle of a pixel to its corners.
+const HALF_DIAGONAL: f64 = std::f64::consts::FRAC_1_SQRT_2;
+
+// Points per arc, as the app uses.
+const ARC_POINTS: usize = 25;
+
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Option<Rgb>) -> Self {
+        let fill = match background {
+            Some(c) => [c.0, c.1, c.2, 255],
+            None => [0, 0, 0, 0],
+        };
+        Image { width, height, pixels: vec![fill; width * height] }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgba {
+        self.pixels[y * self.width + x]
+    }
+
+    // Paint `c` over the pixel, covering `alpha` of it.
+    fn blend(&mut self, x: usize, y: usize, c: Rgb, alpha: f64) {
+        let d = &mut self.pixels[y * self.width + x];
+        let da = d[3] as f64 / 255.;
+        let a = alpha + da * (1. - alpha);
+        if a <= 0. {
+            return
+        }
+        let mix = |s: u8, t: u8| ((s as f64 * alpha + t as f64 * da * (1. - alpha)) / a).round() as u8;
+        *d = [mix(c.0, d[0]), mix(c.1, d[1]), mix(c.2, d[2]), (a * 255.).round() as u8];
+    }
+
+    // Paint `c` over the pixels within `bounds` (in pixels), covering each
+    // by the fraction `coverage` gives for it.
+    fn cover(&mut self, bounds: BoundingBox, c: Rgb, coverage: impl Fn(usize, usize) -> f64) {
+        let x0 = bounds.min.0.floor().max(0.) as usize;
+        let y0 = bounds.min.1.floor().max(0.) as usize;
+        let x1 = (bounds.max.0.ceil().max(0.) as usize).min(self.width);
+        let y1 = (bounds.max.1.ceil().max(0.) as usize).min(self.height);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let alpha = coverage(x, y);
+                if alpha > 0. {
+                    self.blend(x, y, c, alpha);
+                }
+            }
+        }
+    }
+
+    fn fill_polygon(&mut self, pts: &[(f64, f64)], c: Rgb) {
+        let bounds = pixel_bounds(pts, 0.);
+        self.cover(bounds, c, |x, y| sampled(x, y, |p| {
+            // Even-odd crossings of a ray to the right of p.
+            let mut inside = false;
+            for i in 0..pts.len() {
+                let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+                if (a.1 > p.1) != (b.1 > p.1) && p.0 < a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0) {
+                    inside = !inside;
+                }
+            }
+            inside
+        }));
+    }
+
+    fn stroke(&mut self, pts: &[(f64, f64)], closed: bool, weight: f64, c: Rgb) {
+        if weight <= 0. || pts.len() < 2 {
+            return
+        }
+        let r = weight / 2.;
+        let n = if closed { pts.len() } else { pts.len() - 1 };
+        let segments: Vec<((f64, f64), (f64, f64))> = (0..n).map(|i| (pts[i], pts[(i + 1) % pts.len()])).collect();
+        let distance = |p| segments.iter().map(|(a, b)| segment_distance(p, *a, *b)).fold(f64::INFINITY, f64::min);
+        self.cover(pixel_bounds(pts, r), c, |x, y| {
+            // Only pixels that the edge of the line crosses need sampling.
+            let d = distance((x as f64 + 0.5, y as f64 + 0.5));
+            if d > r + HALF_DIAGONAL {
+                0.
+            } else if d < r - HALF_DIAGONAL {
+                1.
+            } else {
+                sampled(x, y, |p| distance(p) <= r)
+            }
+        });
+    }
+
+    /// The image as a PNG file.
+    pub fn encode_png(&self) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bit RGBA, no interlacing.
+        header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+        // Each row starts with filter type 0, none.
+        let mut raw = Vec::with_capacity(self.height * (1 + 4 * self.width));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0);
+            for p in row {
+                raw.extend_from_slice(p);
+            }
+        }
+
+        let mut result = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        write_chunk(&mut result, b"IHDR", &header);
+        write_chunk(&mut result, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut result, b"IEND", &[]);
+        result
+    }
+}
+
+// The fraction of the samples of pixel (x, y) that `inside` accepts.
+fn sampled(x: usize, y: usize, inside: impl Fn((f64, f64)) -> bool) -> f64 {
+    let mut n = 0;
+    for i in 0..SAMPLES {
+        for j in 0..SAMPLES {
+            let p = (x as f64 + (i as f64 + 0.5) / SAMPLES as f64,
+                     y as f64 + (j as f64 + 0.5) / SAMPLES as f64);
+            if inside(p) {
+                n += 1;
+            }
+        }
+    }
+    n as f64 / (SAMPLES * SAMPLES) as f64
+}
+
+fn pixel_bounds(pts: &[(f64, f64)], r: f64) -> BoundingBox {
+    let min = pts.iter().fold((f64::INFINITY, f64::INFINITY), |m, p| (m.0.min(p.0), m.1.min(p.1)));
+    let max = pts.iter().fold((f64::NEG_INFINITY, f64::NEG_INFINITY), |m, p| (m.0.max(p.0), m.1.max(p.1)));
+    BoundingBox::new((min.0 - r, min.1 - r), (max.0 + r, max.1 + r))
+}
+
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 > 0. { (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len2).clamp(0., 1.) } else { 0. };
+    (p.0 - a.0 - t * dx).hypot(p.1 - a.1 - t * dy)
+}
+
+fn arc_points(a: &Arc) -> Vec<(f64, f64)> {
+    let start = a.start_angle as f64;
+    let span = (a.end_angle - a.start_angle).rem_euclid(360) as f64;
+    (0..ARC_POINTS)
+        .map(|i| (start + span * i as f64 / (ARC_POINTS - 1) as f64) * std::f64::consts::PI / 180.)
+        .map(|t| (a.center.0 + a.radius * t.cos(), a.center.1 + a.radius * t.sin()))
+        .collect()
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xedb8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for x in chunk {
+            a += *x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+// A zlib stream holding `bytes` in stored, uncompressed, blocks.
+fn zlib_stored(bytes: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let mut blocks = bytes.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        result.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        result.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let len = block.len() as u16;
+        result.extend_from_slice(&len.to_le_bytes());
+        result.extend_from_slice(&(!len).to_le_bytes());
+        result.extend_from_slice(block);
+    }
+    result.extend_from_slice(&adler32(bytes).to_be_bytes());
+    result
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Draw `tiles`, placed by `transform`, into a `width` by `height` image in
+/// `style`.
+pub fn render(tiles: &[Box<dyn PenroseTile>], transform: &RigidTransform, width: usize, height: usize, scale: f64,
+              style: &Style) -> Image {
+    let mut image = Image::new(width, height, style.background);
+    let to_pixels = |pts: &[(f64, f64)]| -> Vec<(f64, f64)> {
+        pts.iter()
+            .map(|p| transform.apply(*p))
+            .map(|p| (width as f64 / 2. + p.0 * scale, height as f64 / 2. - p.1 * scale))
+            .collect()
+    };
+    for t in tiles {
+        let polygon = to_pixels(&t.vertices());
+        image.fill_polygon(&polygon, style.fill_color(t.kind()));
+        image.stroke(&polygon, true, style.edge_weight, style.edge_color);
+        if style.show_arcs {
+            image.stroke(&to_pixels(&arc_points(&t.get_small_arc())), false, style.arc_weight, style.arc1_color);
+            image.stroke(&to_pixels(&arc_points(&t.get_big_arc())), false, style.arc_weight, style.arc2_color);
+        }
+        if style.show_bars {
+            for bar in t.get_ammann_bars().chunks(2) {
+                image.stroke(&to_pixels(bar), false, style.bar_weight, style.bar_color);
+            }
+        }
+    }
+    image
+}
+
+pub fn save_png<P: AsRef<Path>>(path: P, tiles: &[Box<dyn PenroseTile>], transform: &RigidTransform, width: usize,
+                                height: usize, scale: f64, style: &Style) -> std::io::Result<()> {
+    std::fs::write(path, render(tiles, transform, width, height, scale, style).encode_png())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(&vec![0xff; 100_000]), {
+            let (mut a, mut b) = (1u64, 0u64);
+            for _ in 0..100_000 {
+                a = (a + 0xff) % 65521;
+                b = (b + a) % 65521;
+            }
+            ((b << 16) | a) as u32
+        });
+    }
+
+    // Read back the data of a zlib stream of stored blocks.
+    fn unstore(z: &[u8]) -> Vec<u8> {
+        assert_eq!((z[0] as u32 * 256 + z[1] as u32) % 31, 0);
+        let mut result = Vec::new();
+        let mut i = 2;
+        loop {
+            let last = z[i] & 1 == 1;
+            let len = u16::from_le_bytes([z[i + 1], z[i + 2]]) as usize;
+            assert_eq!(!u16::from_le_bytes([z[i + 3], z[i + 4]]) as usize, len);
+            result.extend_from_slice(&z[i + 5..i + 5 + len]);
+            i += 5 + len;
+            if last {
+                break
+            }
+        }
+        assert_eq!(&z[i..], &adler32(&result).to_be_bytes());
+        result
+    }
+
+    #[test]
+    fn test_png() {
+        let mut image = Image::new(200, 150, Some((1, 2, 3)));
+        image.blend(5, 7, (255, 255, 255), 1.);
+        let png = image.encode_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let mut chunks = Vec::new();
+        let mut i = 8;
+        while i < png.len() {
+            let len = u32::from_be_bytes([png[i], png[i + 1], png[i + 2], png[i + 3]]) as usize;
+            let crc = u32::from_be_bytes([png[i + 8 + len], png[i + 9 + len], png[i + 10 + len], png[i + 11 + len]]);
+            assert_eq!(crc, crc32(&png[i + 4..i + 8 + len]));
+            chunks.push((png[i + 4..i + 8].to_vec(), png[i + 8..i + 8 + len].to_vec()));
+            i += 12 + len;
+        }
+        assert_eq!(chunks.iter().map(|c| c.0.as_slice()).collect::<Vec<_>>(), vec![b"IHDR", b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, [0, 0, 0, 200, 0, 0, 0, 150, 8, 6, 0, 0, 0]);
+
+        // More than one stored block, each row filtered with none.
+        let raw = unstore(&chunks[1].1);
+        assert!(raw.len() > 0xffff);
+        assert_eq!(raw.len(), 150 * (1 + 4 * 200));
+        assert_eq!(raw[0], 0);
+        assert_eq!(&raw[1..5], &[1, 2, 3, 255]);
+        let p = 7 * (1 + 4 * 200) + 1 + 4 * 5;
+        assert_eq!(&raw[p..p + 4], &[255, 255, 255, 255]);
+
+        assert_eq!(unstore(&zlib_stored(&[])), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_render() {
+        let style = Style::default();
+        let sun = sun();
+        let image = render(&sun, &RigidTransform::identity(), 200, 200, 40., &style);
+        let bg = style.background.unwrap();
+        assert_eq!(image.pixel(0, 0), [bg.0, bg.1, bg.2, 255]);
+
+        // Inside a kite, away from its edges and arcs, is the kite colour.
+        let v = sun[0].vertices();
+        let c = v.iter().fold((0., 0.), |s, p| (s.0 + p.0 / 4., s.1 + p.1 / 4.));
+        let (x, y) = ((100. + c.0 * 40.) as usize, (100. - c.1 * 40.) as usize);
+        let k = style.fill_color2;
+        assert_eq!(image.pixel(x, y), [k.0, k.1, k.2, 255]);
+
+        // Edges and arcs only appear when asked for.
+        let count = |image: &Image, c: Rgb| (0..200).flat_map(|y| (0..200).map(move |x| (x, y)))
+            .filter(|(x, y)| image.pixel(*x, *y) == [c.0, c.1, c.2, 255])
+            .count();
+        assert!(count(&image, style.edge_color) > 0);
+        assert!(count(&image, style.arc1_color) > 0);
+        assert_eq!(count(&image, style.bar_color), 0);
+        let plain = Style { edge_weight: 0., show_arcs: false, show_bars: true, background: None, ..Style::default() };
+        let image = render(&sun, &RigidTransform::identity(), 200, 200, 40., &plain);
+        assert_eq!(count(&image, style.edge_color), 0);
+        assert_eq!(count(&image, style.arc1_color), 0);
+        assert!(count(&image, style.bar_color) > 0);
+        assert_eq!(image.pixel(0, 0), [0, 0, 0, 0]);
+
+        // Tiles off the image are clipped.
+        let far = vec![new_tile(Tile::KITE, 100., 100., 0), new_tile(Tile::DART, -100., 0., 0)];
+        let image = render(&far, &RigidTransform::identity(), 20, 20, 40., &plain);
+        assert_eq!(image.pixel(10, 10), [0, 0, 0, 0]);
+
+        // Moved into view, the kite is drawn.
+        let image = render(&far, &RigidTransform::translation(-100., -100.), 20, 20, 40., &plain);
+        assert_eq!(image.pixel(10, 10), [k.0, k.1, k.2, 255]);
+    }
+}
@@ -0,0 +1,617 @@
+//! The Penrose matching rules.
+//!
+//! Two tiles may only share an edge if the edge has the same length and the
+//! same marks at each end, as seen from both tiles (see `Mark`). Tiles that
+//! fit together but break these rules make patches that can never be
+//! extended to a tiling of the plane.
+//!
+//! Even a legal patch may have no completion. `analyse_boundary` looks at
+//! each vertex of the open boundary in turn, as Conway did, and finds every
+//! way of filling the gap there with tiles glued round it. A tile that every
+//! filling places is forced, and a vertex with no filling is a dead end.
+
+use std::collections::HashSet;
+
+use crate::mesh::Mesh;
+use crate::tiles::*;
+
+/// True if `a` and `b` can be glued: they run in opposite directions, have
+/// the same length and their marks agree at both ends. Their positions are
+/// not compared.
+pub fn edges_match(a: &Edge, b: &Edge) -> bool {
+    (a.angle + 180) % 360 == b.angle
+        && a.length == b.length
+        && a.marks.0 == b.marks.1
+        && a.marks.1 == b.marks.0
+}
+
+/// The edges of `tile` that lie on one of `edges` (their centres within
+/// `tol`) without matching it.
+pub fn placement_conflicts(tile: &dyn PenroseTile, edges: &[Edge], tol: f64) -> Vec<i32> {
+    let mut result = Vec::new();
+    for (i, e) in tile.get_edges().iter().enumerate() {
+        let conflict = edges.iter().any(|b| {
+            let dx = b.center.0 - e.center.0;
+            let dy = b.center.1 - e.center.1;
+            dx*dx + dy*dy < tol*tol && !edges_match(e, b)
+        });
+        if conflict {
+            result.push(i as i32 + 1);
+        }
+    }
+    result
+}
+
+/// True if `tile` can be added next to the edges `edges` without breaking
+/// the matching rules.
+pub fn is_legal_placement(tile: &dyn PenroseTile, edges: &[Edge], tol: f64) -> bool {
+    placement_conflicts(tile, edges, tol).is_empty()
+}
+
+/// The pairs of tiles in `tiles`, with their edge numbers, that share an
+/// edge in breach of the matching rules.
+pub fn rule_violations(tiles: &[Box<dyn PenroseTile>], tol: f64) -> Vec<((usize, i32), (usize, i32))> {
+    let edges: Vec<Vec<Edge>> = tiles.iter().map(|t| t.get_edges()).collect();
+    let mut result = Vec::new();
+    for (i, ei) in edges.iter().enumerate() {
+        for (j, ej) in edges.iter().enumerate().skip(i+1) {
+            for (a, x) in ei.iter().enumerate() {
+                for (b, y) in ej.iter().enumerate() {
+                    let dx = x.center.0 - y.center.0;
+                    let dy = x.center.1 - y.center.1;
+                    if dx*dx + dy*dy < tol*tol && !edges_match(x, y) {
+                        result.push(((i, a as i32 + 1), (j, b as i32 + 1)));
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// The edges of `tiles` not shared with another tile.
+pub fn boundary_edges(tiles: &[Box<dyn PenroseTile>]) -> Vec<Edge> {
+    let edges: Vec<Vec<Edge>> = tiles.iter().map(|t| t.get_edges()).collect();
+    let mut result = Vec::new();
+    for (i, ei) in edges.iter().enumerate() {
+        for e in ei {
+            let shared = edges.iter().enumerate().any(|(j, ej)| j != i && ej.iter().any(|f| {
+                let dx = e.center.0 - f.center.0;
+                let dy = e.center.1 - f.center.1;
+                dx*dx + dy*dy < 1e-12 && (e.angle + 180) % 360 == f.angle
+            }));
+            if !shared {
+                result.push(e.clone());
+            }
+        }
+    }
+    result
+}
+
+// Split a tile into triangles, fanning from vertex 0. That is the concave
+// vertex of a dart, so the triangles never stick out of it.
+fn triangulate(t: &dyn PenroseTile) -> Vec<[(f64, f64); 3]> {
+    let v = t.vertices();
+    (1..v.len()-1).map(|i| [v[0], v[i], v[i+1]]).collect()
+}
+
+// True if two triangles overlap in more than an edge or a point.
+fn triangles_overlap(a: &[(f64, f64); 3], b: &[(f64, f64); 3]) -> bool {
+    // Separating axis test over the edge normals of both triangles.
+    let separated = |p: &[(f64, f64); 3], q: &[(f64, f64); 3]| (0..3).any(|i| {
+        let (x, y) = (p[(i+1)%3].0 - p[i].0, p[(i+1)%3].1 - p[i].1);
+        let n = (-y, x);
+        let project = |t: &[(f64, f64); 3]| {
+            let d: Vec<f64> = t.iter().map(|v| n.0*v.0 + n.1*v.1).collect();
+            (d.iter().cloned().fold(f64::INFINITY, f64::min), d.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
+        };
+        let (p0, p1) = project(p);
+        let (q0, q1) = project(q);
+        p1 <= q0 + 1e-9 || q1 <= p0 + 1e-9
+    });
+    !separated(a, b) && !separated(b, a)
+}
+
+// Tiles whose centres are further apart than this, a little over two long
+// edges, cannot touch.
+const TOUCH_REACH: f64 = 5.3;
+
+// True if `t`, cut into `pieces`, overlaps `u`.
+fn overlaps_tile(t: &dyn PenroseTile, pieces: &[[(f64, f64); 3]], u: &dyn PenroseTile) -> bool {
+    let (c, d) = (t.center(), u.center());
+    if (c.0 - d.0).hypot(c.1 - d.1) > TOUCH_REACH {
+        return false
+    }
+    triangulate(u).iter().any(|q| pieces.iter().any(|p| triangles_overlap(p, q)))
+}
+
+/// True if `t` overlaps any of `tiles`.
+pub fn overlaps(t: &dyn PenroseTile, tiles: &[Box<dyn PenroseTile>]) -> bool {
+    let pieces = triangulate(t);
+    tiles.iter().any(|u| overlaps_tile(t, &pieces, u.as_ref()))
+}
+
+/// True if `t` overlaps any tile of `mesh`. Only the tiles near it are
+/// looked at.
+pub fn overlaps_mesh(t: &dyn PenroseTile, mesh: &Mesh) -> bool {
+    let pieces = triangulate(t);
+    mesh.faces_near(t.center(), TOUCH_REACH).iter().any(|f| overlaps_tile(t, &pieces, mesh.faces()[*f].tile.as_ref()))
+}
+
+/// The tiles a patch is drawn from, as kinds and whether they are
+/// reflected: every kind in the tile set of each of its tiles, reflected if
+/// any tile of that set is, and unreflected if any is not. An empty patch is
+/// taken to be unreflected kites and darts.
+pub fn tile_set(tiles: &[Box<dyn PenroseTile>]) -> Vec<(Tile, bool)> {
+    let sets = [vec![Tile::KITE, Tile::DART],
+                vec![Tile::FAT, Tile::SKINNY],
+                vec![Tile::ROBINSON_A, Tile::ROBINSON_A_MIRROR, Tile::ROBINSON_B, Tile::ROBINSON_B_MIRROR]];
+    let mut result = Vec::new();
+    for set in sets.iter() {
+        for reflected in [false, true].iter() {
+            if tiles.iter().any(|t| set.contains(&t.kind()) && t.reflected() == *reflected) {
+                result.extend(set.iter().map(|k| (*k, *reflected)));
+            }
+        }
+    }
+    if result.is_empty() { sets[0].iter().map(|k| (*k, false)).collect() } else { result }
+}
+
+// True if `a` and `b` are the same tile in the same place as far as the
+// matching rules go: the same kind, with the same edges and marks. A
+// reflected tile is always the same as some unreflected one, which may be
+// turned another way.
+fn same_tile(a: &dyn PenroseTile, b: &dyn PenroseTile) -> bool {
+    let edges = b.get_edges();
+    a.kind() == b.kind() && a.get_edges().iter().all(|e| edges.iter().any(|f| {
+        same_point(e.center, f.center) && e.angle == f.angle && e.length == f.length && e.marks == f.marks
+    }))
+}
+
+/// The ways of filling the gap at one vertex of a patch's boundary.
+pub struct VertexFit {
+    /// The boundary edge that ends at the vertex. The gap runs from it round
+    /// to the next boundary edge, on the side away from the patch.
+    pub edge: Edge,
+    pub point: (f64, f64),
+    /// Every legal filling, as the tiles glued round the vertex in order
+    /// from `edge`.
+    pub completions: Vec<Vec<Box<dyn PenroseTile>>>,
+}
+
+impl VertexFit {
+    /// The tiles that every filling places, so any completion of the patch
+    /// does.
+    pub fn forced(&self) -> Vec<&dyn PenroseTile> {
+        match self.completions.split_first() {
+            Some((first, rest)) => first.iter()
+                .map(|t| t.as_ref())
+                .filter(|t| rest.iter().all(|c| c.iter().any(|u| same_tile(*t, u.as_ref()))))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn is_forced(&self) -> bool {
+        !self.forced().is_empty()
+    }
+
+    /// Nothing fills the gap, so the patch cannot be completed.
+    pub fn is_dead_end(&self) -> bool {
+        self.completions.is_empty()
+    }
+}
+
+// A `kind` tile, reflected or not.
+fn oriented_tile(kind: Tile, reflected: bool, x: f64, y: f64, angle: i32) -> Box<dyn PenroseTile> {
+    let t = new_tile(kind, x, y, angle);
+    if reflected { reflect_tile(t.as_ref()) } else { t }
+}
+
+// Every way of putting one of `kinds` against `edge` legally, without
+// overlapping a tile of `mesh` or breaking the rules along its open edges.
+fn fits(edge: &Edge, kinds: &[(Tile, bool)], mesh: &Mesh) -> Vec<Box<dyn PenroseTile>> {
+    let mut result: Vec<Box<dyn PenroseTile>> = Vec::new();
+    for (kind, reflected) in kinds {
+        let probe = oriented_tile(*kind, *reflected, 0., 0., 0);
+        for (i, e) in probe.get_edges().iter().enumerate() {
+            let angle = (edge.angle + 180 - e.angle).rem_euclid(360);
+            let turned = oriented_tile(*kind, *reflected, 0., 0., angle);
+            let c = turned.edge_center(i as i32 + 1).unwrap();
+            let t = oriented_tile(*kind, *reflected, edge.center.0 - c.0, edge.center.1 - c.1, angle);
+            if !edges_match(&t.get_edges()[i], edge) {
+                continue
+            }
+            // Edges the tile can lie on have their centres within a long edge
+            // of its own.
+            let edges: Vec<Edge> = mesh.boundary_near(t.center(), 3.).iter().map(|h| mesh.edge(*h)).collect();
+            // In a patch with tiles both ways round, the reflected tile may
+            // be one already found.
+            if is_legal_placement(t.as_ref(), &edges, 1e-6) && !overlaps_mesh(t.as_ref(), mesh)
+                && !result.iter().any(|u| same_tile(u.as_ref(), t.as_ref())) {
+                result.push(t);
+            }
+        }
+    }
+    result
+}
+
+// The most tiles that can meet at a vertex: ten half-kites' tails.
+const MAX_CORNERS: usize = 10;
+
+// How close two corners must be to be the same point.
+fn same_point(p: (f64, f64), q: (f64, f64)) -> bool {
+    (p.0 - q.0).hypot(p.1 - q.1) < 1e-6
+}
+
+// Fill the gap at `p`, after the tiles in `placed`, by gluing each tile that
+// fits against `edge`, which ends at `p`. A tile whose other edge at `p` lies
+// on an open edge of the mesh closes the gap; otherwise the tile is added to
+// the mesh and the search goes on from that edge.
+fn fill_vertex(edge: &Edge, p: (f64, f64), kinds: &[(Tile, bool)], mesh: &mut Mesh,
+               placed: &mut Vec<Box<dyn PenroseTile>>, result: &mut Vec<Vec<Box<dyn PenroseTile>>>) {
+    for t in fits(edge, kinds, mesh) {
+        let next = match t.get_edges().into_iter().find(|e| same_point(e.end_points().1, p)) {
+            Some(e) => e,
+            None => continue,
+        };
+        if !mesh.boundary_near(next.center, 1e-6).is_empty() {
+            let mut done: Vec<Box<dyn PenroseTile>> = placed.iter().map(|u| copy_tile(u.as_ref())).collect();
+            done.push(t);
+            result.push(done);
+        } else if placed.len() + 1 < MAX_CORNERS {
+            mesh.add_tile(t.as_ref());
+            placed.push(t);
+            fill_vertex(&next, p, kinds, mesh, placed, result);
+            placed.pop();
+            mesh.pop_tile();
+        }
+    }
+}
+
+// The gap at the end of the open edge `edge` of `mesh`, filled every legal
+// way. The mesh is put back as it was.
+fn analyse_vertex(edge: &Edge, kinds: &[(Tile, bool)], mesh: &mut Mesh) -> VertexFit {
+    let p = edge.end_points().1;
+    let mut completions = Vec::new();
+    fill_vertex(edge, p, kinds, mesh, &mut Vec::new(), &mut completions);
+    VertexFit { edge: edge.clone(), point: p, completions }
+}
+
+/// The ways of filling the gap at each vertex of the open boundary `edges`
+/// of `tiles`, which is the output of `boundary_edges`. There is one gap for
+/// each edge, at the vertex it runs to.
+///
+/// Only the gap itself is filled, so a filling found here may still lead to
+/// a dead end further along the boundary.
+pub fn analyse_boundary(tiles: &[Box<dyn PenroseTile>], edges: &[Edge]) -> Vec<VertexFit> {
+    let kinds = tile_set(tiles);
+    let mut mesh = Mesh::from_tiles(tiles);
+    edges.iter().map(|e| analyse_vertex(e, &kinds, &mut mesh)).collect()
+}
+
+// The forced tiles of `fits`, each once.
+fn distinct_forced(fits: &[VertexFit]) -> Vec<Box<dyn PenroseTile>> {
+    let mut result: Vec<Box<dyn PenroseTile>> = Vec::new();
+    for fit in fits {
+        for t in fit.forced() {
+            if !result.iter().any(|u| same_tile(t, u.as_ref())) {
+                result.push(copy_tile(t));
+            }
+        }
+    }
+    result
+}
+
+/// The forced tiles round the boundary, each once.
+pub fn forced_tiles(tiles: &[Box<dyn PenroseTile>], edges: &[Edge]) -> Vec<Box<dyn PenroseTile>> {
+    distinct_forced(&analyse_boundary(tiles, edges))
+}
+
+// Each point once.
+fn distinct_points(points: impl Iterator<Item = (f64, f64)>) -> Vec<(f64, f64)> {
+    let mut result: Vec<(f64, f64)> = Vec::new();
+    for p in points {
+        if !result.iter().any(|q| same_point(p, *q)) {
+            result.push(p);
+        }
+    }
+    result
+}
+
+/// The boundary vertices where the gap can't be filled.
+pub fn dead_ends(tiles: &[Box<dyn PenroseTile>], edges: &[Edge]) -> Vec<(f64, f64)> {
+    distinct_points(analyse_boundary(tiles, edges).into_iter().filter(|f| f.is_dead_end()).map(|f| f.point))
+}
+
+// How far from a boundary edge the tiles that decide how the gap at its end
+// can be filled can be: the filling reaches a long edge and a bit from the
+// vertex, and can overlap tiles whose centres are a little over two long
+// edges from its own.
+const FIT_REACH: f64 = 10.;
+
+// How far an edge's centre can be from its end: half a long edge, phi^2 / 2.
+const HALF_EDGE: f64 = 1.31;
+
+/// The gaps at the boundary vertices of `mesh` within `r` of any of
+/// `points`, each once, as `analyse_boundary` finds them. Only the tiles
+/// near those vertices are looked at, so the cost goes with how much of the
+/// boundary is asked about and not with the size of the patch.
+pub fn analyse_near(mesh: &Mesh, points: &[(f64, f64)], r: f64) -> Vec<VertexFit> {
+    let mut halves: Vec<usize> = points.iter()
+        .flat_map(|p| mesh.boundary_near(*p, r + HALF_EDGE).into_iter().filter(move |h| {
+            let q = mesh.edge(*h).end_points().1;
+            (q.0 - p.0).hypot(q.1 - p.1) < r
+        }))
+        .collect::<HashSet<usize>>()
+        .into_iter()
+        .collect();
+    halves.sort();
+    let edges: Vec<Edge> = halves.iter().map(|h| mesh.edge(*h)).collect();
+    let mut faces: Vec<usize> = edges.iter()
+        .flat_map(|e| mesh.faces_near(e.center, FIT_REACH))
+        .collect::<HashSet<usize>>()
+        .into_iter()
+        .collect();
+    faces.sort();
+    let tiles: Vec<Box<dyn PenroseTile>> = faces.iter().map(|f| copy_tile(mesh.faces()[*f].tile.as_ref())).collect();
+    let kinds = tile_set(&tiles);
+    let mut local = Mesh::from_tiles(&tiles);
+    edges.iter().map(|e| analyse_vertex(e, &kinds, &mut local)).collect()
+}
+
+/// The dead ends among the boundary vertices of `mesh` within `r` of any of
+/// `points`.
+pub fn dead_ends_near(mesh: &Mesh, points: &[(f64, f64)], r: f64) -> Vec<(f64, f64)> {
+    distinct_points(analyse_near(mesh, points, r).into_iter().filter(|f| f.is_dead_end()).map(|f| f.point))
+}
+
+/// The forced tiles at the boundary vertices of `mesh` within `r` of any of
+/// `points`, each once.
+pub fn forced_tiles_near(mesh: &Mesh, points: &[(f64, f64)], r: f64) -> Vec<Box<dyn PenroseTile>> {
+    distinct_forced(&analyse_near(mesh, points, r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::*;
+    use crate::deflate::tests::*;
+    use crate::deflate::*;
+    use crate::exact::ExactPoint;
+
+    #[test]
+    fn test_deflated_patches_are_legal() {
+        let patches = [deflate_n(&sun(), 4),
+                           deflate_n(&star(), 4),
+                           deflate_n(&[new_exact_tile(Tile::FAT, ExactPoint::origin(), 0)], 4),
+                           deflate_n(&[new_exact_tile(Tile::SKINNY, ExactPoint::origin(), 0)], 4),
+                           to_triangles(&deflate_n(&sun(), 3))];
+        for tiles in patches.iter() {
+            assert!(rule_violations(tiles, 1e-6).is_empty());
+
+            // Every shared edge is matched by exactly one other tile.
+            let edges: Vec<Edge> = tiles.iter().flat_map(|t| t.get_edges()).collect();
+            let shared = edges.iter().filter(|a| edges.iter().any(|b| {
+                (a.center.0 - b.center.0).abs() < 1e-6 && (a.center.1 - b.center.1).abs() < 1e-6
+                    && edges_match(a, b)
+            })).count();
+            assert!(shared * 2 > edges.len());
+        }
+    }
+
+    #[test]
+    fn test_marks() {
+        // Edges 1 and 4 of a kite meet at its tail, which is H, as are both
+        // ends of a half-kite's axis.
+        let k = Kite::new(0., 0., 0);
+        assert_eq!(k.edge_marks(1), Ok((Mark::H, Mark::T)));
+        assert_eq!(k.edge_marks(4), Ok((Mark::T, Mark::H)));
+        assert_eq!(k.edge_marks(5), Err(5));
+        let a = RobinsonA::new(0., 0., 0);
+        assert_eq!(a.edge_marks(3), Ok((Mark::H, Mark::H)));
+        assert_eq!(a.mirror().edge_marks(1), Ok((Mark::H, Mark::H)));
+
+        let f = Fat::new(0., 0., 0);
+        let arcs = (1..5).filter(|e| f.edge_marks(*e).unwrap() != (Mark::Plain, Mark::SmallArc)
+                                      && f.edge_marks(*e).unwrap() != (Mark::SmallArc, Mark::Plain)).count();
+        assert_eq!(arcs, 2);
+    }
+
+    #[test]
+    fn test_illegal_placement() {
+        // Two kites may share their long edges 1 and 4 only as in a sun:
+        // flipping the neighbour puts its head against the tail.
+        let k1 = Kite::new_exact(ExactPoint::origin(), 0);
+        let edge = k1.get_edges().remove(0);
+        let legal = place_kite_edge_exact(4, k1.exact_edge_center(1).unwrap(), edge.angle).unwrap();
+        let illegal = place_kite_edge_exact(1, k1.exact_edge_center(1).unwrap(), edge.angle).unwrap();
+        let edges = k1.get_edges();
+        assert!(is_legal_placement(&legal, &edges, 1e-6));
+        assert_eq!(placement_conflicts(&illegal, &edges, 1e-6), vec![1]);
+
+        let tiles: Vec<Box<dyn PenroseTile>> = vec![Box::new(k1), Box::new(illegal)];
+        assert_eq!(rule_violations(&tiles, 1e-6), vec![((0, 1), (1, 1))]);
+
+        // Nor does an edge laid across another at the wrong angle.
+        let e2 = tiles[0].get_edges().remove(1);
+        let d = place_dart_edge_exact(2, tiles[0].exact_edge_center(2).unwrap(), (e2.angle + 36) % 360).unwrap();
+        assert!(!is_legal_placement(&d, &tiles[0].get_edges(), 1e-6));
+    }
+
+    // The tiles of a legal patch within `r` of its centre.
+    fn disc(tiles: &[Box<dyn PenroseTile>], r: f64) -> Vec<Box<dyn PenroseTile>> {
+        tiles.iter().filter(|t| t.center().0.hypot(t.center().1) < r).map(|t| copy_tile(t.as_ref())).collect()
+    }
+
+    #[test]
+    fn test_cut_patches() {
+        // A piece cut from a legal tiling can be completed, and a forced
+        // tile must be the one the tiling has there.
+        let skinny = new_exact_tile(Tile::SKINNY, ExactPoint::origin(), 0);
+        for seed in [sun(), star(), vec![new_exact_tile(Tile::FAT, ExactPoint::origin(), 0)],
+                     vec![reflect_tile(skinny.as_ref())]].iter() {
+            let full = deflate_n(seed, 5);
+            let part = disc(&full, 6.);
+            let edges = boundary_edges(&part);
+            assert!(!edges.is_empty());
+            assert!(dead_ends(&part, &edges).is_empty());
+
+            let forced = forced_tiles(&part, &edges);
+            assert!(!forced.is_empty());
+            for t in forced.iter() {
+                assert!(full.iter().any(|u| same_tile(t.as_ref(), u.as_ref())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_hole() {
+        // The hole left by one tile can only be filled by that tile.
+        let mut tiles = disc(&deflate_n(&sun(), 4), 5.);
+        let removed = tiles.remove(tiles.len() / 2);
+        let edges = boundary_edges(&tiles);
+        let hole: Vec<VertexFit> = analyse_boundary(&tiles, &edges).into_iter()
+            .filter(|f| removed.get_edges().iter().any(|e| {
+                (e.center.0 - f.edge.center.0).abs() < 1e-6 && (e.center.1 - f.edge.center.1).abs() < 1e-6
+            }))
+            .collect();
+        assert_eq!(hole.len(), removed.get_edges().len());
+        for f in hole.iter() {
+            assert_eq!(f.completions.len(), 1);
+            let forced = f.forced();
+            assert_eq!(forced.len(), 1);
+            assert_eq!(forced[0].kind(), removed.kind());
+            assert_eq!(forced[0].angle(), removed.angle());
+        }
+    }
+
+    #[test]
+    fn test_forced_vertex() {
+        // Only two kites fill the gap at a dart's concave vertex, making an
+        // ace.
+        let dart = new_tile(Tile::DART, 0., 0., 0);
+        let tiles = vec![copy_tile(dart.as_ref())];
+        let edges = boundary_edges(&tiles);
+        let fits = analyse_boundary(&tiles, &edges);
+        let ace = fits.iter().find(|f| same_point(f.point, dart.vertices()[0])).unwrap();
+        assert_eq!(ace.completions.len(), 1);
+        let forced = ace.forced();
+        assert_eq!(forced.len(), 2);
+        assert!(forced.iter().all(|t| t.kind() == Tile::KITE));
+        assert!(rule_violations(&ace.completions[0], 1e-6).is_empty());
+        assert!(fits.iter().all(|f| !f.is_dead_end()));
+        assert_eq!(forced_tiles(&tiles, &edges).len(), 2);
+    }
+
+    #[test]
+    fn test_mixed_reflection() {
+        // A reflected kite far away doesn't change what the dart forces,
+        // though the gaps are filled with tiles both ways round.
+        let near_dart = |tiles: &[Box<dyn PenroseTile>]| {
+            forced_tiles(tiles, &boundary_edges(tiles)).into_iter()
+                .filter(|t| t.center().0.hypot(t.center().1) < 5.)
+                .count()
+        };
+        let mut tiles = vec![new_tile(Tile::DART, 0., 0., 0), new_tile(Tile::KITE, 20., 0., 0)];
+        let forced = near_dart(&tiles);
+        assert!(forced > 0);
+        tiles.push(reflect_tile(new_tile(Tile::KITE, -20., 0., 108).as_ref()));
+        assert_eq!(tile_set(&tiles).len(), 4);
+        assert_eq!(near_dart(&tiles), forced);
+
+        // A reflected skinny rhombus is an unreflected one turned.
+        let skinny = new_tile(Tile::SKINNY, 1., 2., 72);
+        assert!(same_tile(reflect_tile(skinny.as_ref()).as_ref(), new_tile(Tile::SKINNY, 1., 2., 216).as_ref()));
+        assert!(!same_tile(reflect_tile(skinny.as_ref()).as_ref(), skinny.as_ref()));
+    }
+
+    #[test]
+    fn test_tile_set() {
+        let mixed = vec![new_tile(Tile::SKINNY, 0., 0., 0), new_tile(Tile::KITE, 5., 0., 0)];
+        assert_eq!(tile_set(&mixed), vec![(Tile::KITE, false), (Tile::DART, false),
+                                          (Tile::FAT, false), (Tile::SKINNY, false)]);
+        assert_eq!(tile_set(&[new_tile(Tile::ROBINSON_B, 0., 0., 0)]).len(), 4);
+        assert_eq!(tile_set(&[]), vec![(Tile::KITE, false), (Tile::DART, false)]);
+        let mirrored = vec![reflect_tile(mixed[0].as_ref())];
+        assert_eq!(tile_set(&mirrored), vec![(Tile::FAT, true), (Tile::SKINNY, true)]);
+    }
+
+    #[test]
+    fn test_dead_end() {
+        // Two darts against the same side of a kite: every edge is legal,
+        // but they leave a gap that nothing can fill.
+        let tiles = vec![new_tile(Tile::KITE, 0., 0., 0),
+                         new_tile(Tile::DART, 0.5, -1.5388417685876268, 324),
+                         new_tile(Tile::DART, -1.118033988749895, -1.5388417685876268, 0)];
+        assert!(rule_violations(&tiles, 1e-6).is_empty());
+        assert!(!overlaps(tiles[2].as_ref(), &tiles[..2]));
+        let edges = boundary_edges(&tiles);
+        assert!(!dead_ends(&tiles, &edges).is_empty());
+    }
+
+    #[test]
+    fn test_analyse_near_kite() {
+        // Asked from its corners, or from its edge centres far enough out to
+        // reach the tail between the long edges, a lone kite has the same
+        // four gaps either way.
+        let tiles = vec![new_tile(Tile::KITE, 0., 0., 0)];
+        let mesh = Mesh::from_tiles(&tiles);
+        let edges = boundary_edges(&tiles);
+        let key = |f: &VertexFit| ((f.point.0 * 1e6).round() as i64, (f.point.1 * 1e6).round() as i64,
+                                    f.completions.len());
+        let mut expected: Vec<_> = analyse_boundary(&tiles, &edges).iter().map(key).collect();
+        expected.sort();
+        assert_eq!(expected.len(), 4);
+        let corners: Vec<(f64, f64)> = edges.iter().map(|e| e.end_points().1).collect();
+        let centres: Vec<(f64, f64)> = edges.iter().map(|e| e.center).collect();
+        for (points, r) in [(corners, 0.1), (centres, 1.5)].iter() {
+            let mut local: Vec<_> = analyse_near(&mesh, points, *r).iter().map(key).collect();
+            local.sort();
+            assert_eq!(local, expected);
+        }
+    }
+
+    #[test]
+    fn test_dead_ends_near() {
+        // A patch with the dead end above beside a cut piece of a sun, and
+        // the dead ends and forced tiles found locally are the ones found
+        // over the patch.
+        let mut tiles: Vec<Box<dyn PenroseTile>> = deflate_n(&sun(), 3).into_iter().skip(12).collect();
+        tiles.push(new_tile(Tile::KITE, 20., 0., 0));
+        tiles.push(new_tile(Tile::DART, 20.5, -1.5388417685876268, 324));
+        tiles.push(new_tile(Tile::DART, 18.881966011250105, -1.5388417685876268, 0));
+        let mesh = Mesh::from_tiles(&tiles);
+        let all = dead_ends(&tiles, &boundary_edges(&tiles));
+        assert!(!all.is_empty());
+        let fits = analyse_boundary(&tiles, &boundary_edges(&tiles));
+        for (p, r) in [((0., 0.), 4.), ((20., -1.), 3.), ((5., 5.), 6.), ((10., 0.), 30.)].iter() {
+            let key = |q: &(f64, f64)| ((q.0 * 1e6).round() as i64, (q.1 * 1e6).round() as i64);
+            let mut local: Vec<_> = dead_ends_near(&mesh, &[*p], *r).iter().map(key).collect();
+            let mut expected: Vec<_> = all.iter()
+                .filter(|q| (q.0 - p.0).hypot(q.1 - p.1) < *r)
+                .map(key)
+                .collect();
+            local.sort();
+            expected.sort();
+            assert_eq!(local, expected);
+
+            let tile_key = |t: &dyn PenroseTile| (t.kind() as i32, t.angle(), key(&t.center()));
+            let mut local: Vec<_> = forced_tiles_near(&mesh, &[*p], *r).iter().map(|t| tile_key(t.as_ref())).collect();
+            let mut expected: Vec<_> = fits.iter()
+                .filter(|f| (f.point.0 - p.0).hypot(f.point.1 - p.1) < *r)
+                .flat_map(|f| f.forced())
+                .map(tile_key)
+                .collect();
+            local.sort();
+            expected.sort();
+            expected.dedup();
+            assert_eq!(local, expected);
+        }
+
+        // Asking about several points finds each vertex once.
+        let apart = [dead_ends_near(&mesh, &[(0., 0.)], 4.), dead_ends_near(&mesh, &[(20., -1.)], 4.)].concat();
+        assert!(!apart.is_empty());
+        assert_eq!(dead_ends_near(&mesh, &[(0., 0.), (20., -1.), (20., -1.)], 4.).len(), apart.len());
+    }
+}
@@ -0,0 +1,125 @@
+//! A spatial hash for finding things near a point.
+//!
+//! Items are stored at a point in square cells of a fixed size, so finding
+//! the items within a radius only looks at the cells the radius reaches.
+//! Adding and removing are constant time, and a query costs the number of
+//! items in the cells it visits.
+
+use std::collections::HashMap;
+
+// The items in one cell and where they are.
+type Cell<T> = Vec<((f64, f64), T)>;
+
+pub struct SpatialHash<T> {
+    size: f64,
+    cells: HashMap<(i64, i64), Cell<T>>,
+    len: usize,
+}
+
+impl<T: Copy + PartialEq> SpatialHash<T> {
+    /// An empty hash with cells of side `size`, which should be about the
+    /// radius of the usual query.
+    pub fn new(size: f64) -> Self {
+        SpatialHash { size, cells: HashMap::new(), len: 0 }
+    }
+
+    fn cell(&self, p: (f64, f64)) -> (i64, i64) {
+        ((p.0 / self.size).floor() as i64, (p.1 / self.size).floor() as i64)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+    }
+
+    pub fn insert(&mut self, p: (f64, f64), item: T) {
+        let c = self.cell(p);
+        self.cells.entry(c).or_default().push((p, item));
+        self.len += 1;
+    }
+
+    /// Remove `item`, which was inserted at `p`. Returns false if it isn't
+    /// there.
+    pub fn remove(&mut self, p: (f64, f64), item: T) -> bool {
+        let c = self.cell(p);
+        let found = match self.cells.get_mut(&c) {
+            Some(items) => match items.iter().position(|(_, i)| *i == item) {
+                Some(k) => {
+                    items.swap_remove(k);
+                    if items.is_empty() {
+                        self.cells.remove(&c);
+                    }
+                    true
+                }
+                None => false,
+            },
+            None => false,
+        };
+        if found {
+            self.len -= 1;
+        }
+        found
+    }
+
+    /// The items within `r` of `p`.
+    pub fn near(&self, p: (f64, f64), r: f64) -> Vec<T> {
+        let lo = self.cell((p.0 - r, p.1 - r));
+        let hi = self.cell((p.0 + r, p.1 + r));
+        let mut result = Vec::new();
+        for x in lo.0..=hi.0 {
+            for y in lo.1..=hi.1 {
+                if let Some(items) = self.cells.get(&(x, y)) {
+                    result.extend(items.iter()
+                        .filter(|(q, _)| (p.0 - q.0).hypot(p.1 - q.1) < r)
+                        .map(|(_, i)| *i));
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spatial_hash() {
+        let mut hash = SpatialHash::new(2.);
+        let points: Vec<(f64, f64)> = (0..400)
+            .map(|i| ((i % 20) as f64 * 0.7 - 7., (i / 20) as f64 * 0.9 - 9.))
+            .collect();
+        for (i, p) in points.iter().enumerate() {
+            hash.insert(*p, i);
+        }
+        assert_eq!(hash.len(), 400);
+
+        // The same as looking at every point.
+        for (q, r) in [((0., 0.), 1.5), ((-7., -9.), 3.), ((2.3, -4.1), 0.5), ((50., 50.), 5.)].iter() {
+            let mut found = hash.near(*q, *r);
+            found.sort();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|i| (points[*i].0 - q.0).hypot(points[*i].1 - q.1) < *r)
+                .collect();
+            assert_eq!(found, expected);
+        }
+
+        assert!(hash.remove(points[10], 10));
+        assert!(!hash.remove(points[10], 10));
+        assert!(!hash.remove((40., 40.), 12));
+        assert_eq!(hash.len(), 399);
+        assert!(!hash.near(points[10], 0.1).contains(&10));
+
+        hash.clear();
+        assert!(hash.is_empty());
+        assert!(hash.near((0., 0.), 100.).is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+//! Colours and line weights for drawing a patch outside the app.
+//!
+//! These mirror the app's `DrawProps`, and the default is how the app draws
+//! placed tiles.
+
+use crate::tiles::*;
+
+pub type Rgb = (u8, u8, u8);
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Style {
+    /// Left transparent if `None`.
+    pub background: Option<Rgb>,
+    pub fill_color1: Rgb,
+    /// The fill of kites, skinny rhombi and half-kites.
+    pub fill_color2: Rgb,
+    pub edge_color: Rgb,
+    pub arc1_color: Rgb,
+    pub arc2_color: Rgb,
+    pub bar_color: Rgb,
+    /// Edges are left out when this is 0.
+    pub edge_weight: f64,
+    pub arc_weight: f64,
+    pub bar_weight: f64,
+    pub show_arcs: bool,
+    pub show_bars: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            background: Some((100, 149, 237)),
+            fill_color1: (255, 250, 205),
+            fill_color2: (250, 240, 230),
+            edge_color: (230, 230, 250),
+            arc1_color: (255, 182, 193),
+            arc2_color: (152, 251, 152),
+            bar_color: (176, 196, 222),
+            edge_weight: 2.,
+            arc_weight: 2.,
+            bar_weight: 1.,
+            show_arcs: true,
+            show_bars: false,
+        }
+    }
+}
+
+impl Style {
+    pub fn fill_color(&self, kind: Tile) -> Rgb {
+        match kind {
+            Tile::KITE | Tile::SKINNY | Tile::ROBINSON_A | Tile::ROBINSON_A_MIRROR => self.fill_color2,
+            Tile::DART | Tile::FAT | Tile::ROBINSON_B | Tile::ROBINSON_B_MIRROR => self.fill_color1,
+        }
+    }
+}
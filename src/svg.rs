@@ -0,0 +1,210 @@
+//! SVG export.
+//!
+//! A patch is drawn as it is in the app, placed by a `RigidTransform`, with
+//! y up, at `scale` pixels per unit and with a margin around the tiles. The
+//! tiles go in one group, then the arcs and the Ammann bars in groups of
+//! their own on top, so that a later tile never hides the decoration of an
+//! earlier one. Arcs are drawn as true circular arcs.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::style::*;
+use crate::tiles::*;
+use crate::transform::*;
+
+/// The space left around the tiles, in pixels.
+pub const SVG_MARGIN: f64 = 10.;
+
+fn hex(c: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2)
+}
+
+// Maps patch coordinates to SVG ones, which have y down. `min` and `max`
+// bound the placed patch.
+struct Frame {
+    transform: RigidTransform,
+    min: (f64, f64),
+    max: (f64, f64),
+    scale: f64,
+}
+
+impl Frame {
+    fn point(&self, p: (f64, f64)) -> (f64, f64) {
+        let p = self.transform.apply(p);
+        ((p.0 - self.min.0) * self.scale + SVG_MARGIN, (self.max.1 - p.1) * self.scale + SVG_MARGIN)
+    }
+
+    fn size(&self) -> (f64, f64) {
+        ((self.max.0 - self.min.0) * self.scale + 2. * SVG_MARGIN,
+         (self.max.1 - self.min.1) * self.scale + 2. * SVG_MARGIN)
+    }
+
+    // An arc runs anticlockwise from its start angle to its end angle, which
+    // is clockwise once y points down. Turning the patch moves its ends but
+    // not its shape; mirroring it makes the arc run the other way.
+    fn arc(&self, a: &Arc) -> String {
+        let at = |angle: i32| {
+            let r = angle as f64 * std::f64::consts::PI / 180.;
+            self.point((a.center.0 + a.radius * r.cos(), a.center.1 + a.radius * r.sin()))
+        };
+        let (p, q) = (at(a.start_angle), at(a.end_angle));
+        let large = (a.end_angle - a.start_angle).rem_euclid(360) > 180;
+        let sweep = self.transform.reflected;
+        let r = a.radius * self.scale;
+        format!("M {:.3} {:.3} A {:.3} {:.3} 0 {} {} {:.3} {:.3}", p.0, p.1, r, r, large as i32, sweep as i32, q.0, q.1)
+    }
+}
+
+/// The SVG text for `tiles`, placed by `transform`, drawn in `style`.
+pub fn write_svg(tiles: &[Box<dyn PenroseTile>], transform: &RigidTransform, scale: f64, style: &Style) -> String {
+    let bounds = BoundingBox::around_points(tiles.iter().flat_map(|t| transform.vertices(t.as_ref())))
+        .unwrap_or_else(|| BoundingBox::around((0., 0.), 0.));
+    let frame = Frame { transform: *transform, min: bounds.min, max: bounds.max, scale };
+    let (width, height) = frame.size();
+
+    let mut result = String::new();
+    writeln!(result, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(result, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.3}\" height=\"{:.3}\" viewBox=\"0 0 {:.3} {:.3}\">",
+             width, height, width, height).unwrap();
+    if let Some(c) = style.background {
+        writeln!(result, "  <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", hex(c)).unwrap();
+    }
+
+    if style.edge_weight > 0. {
+        writeln!(result, "  <g stroke=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"miter\">",
+                 hex(style.edge_color), style.edge_weight).unwrap();
+    } else {
+        writeln!(result, "  <g stroke=\"none\">").unwrap();
+    }
+    for t in tiles {
+        let points: Vec<String> = t.vertices().iter()
+            .map(|p| frame.point(*p))
+            .map(|p| format!("{:.3},{:.3}", p.0, p.1))
+            .collect();
+        writeln!(result, "    <polygon points=\"{}\" fill=\"{}\"/>", points.join(" "), hex(style.fill_color(t.kind()))).unwrap();
+    }
+    writeln!(result, "  </g>").unwrap();
+
+    if style.show_arcs {
+        writeln!(result, "  <g fill=\"none\" stroke-width=\"{}\">", style.arc_weight).unwrap();
+        for t in tiles {
+            writeln!(result, "    <path d=\"{}\" stroke=\"{}\"/>", frame.arc(&t.get_small_arc()), hex(style.arc1_color)).unwrap();
+            writeln!(result, "    <path d=\"{}\" stroke=\"{}\"/>", frame.arc(&t.get_big_arc()), hex(style.arc2_color)).unwrap();
+        }
+        writeln!(result, "  </g>").unwrap();
+    }
+
+    if style.show_bars {
+        writeln!(result, "  <g stroke=\"{}\" stroke-width=\"{}\">", hex(style.bar_color), style.bar_weight).unwrap();
+        for t in tiles {
+            for bar in t.get_ammann_bars().chunks(2) {
+                let (p, q) = (frame.point(bar[0]), frame.point(bar[1]));
+                writeln!(result, "    <line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\"/>", p.0, p.1, q.0, q.1).unwrap();
+            }
+        }
+        writeln!(result, "  </g>").unwrap();
+    }
+
+    writeln!(result, "</svg>").unwrap();
+    result
+}
+
+pub fn save_svg<P: AsRef<Path>>(path: P, tiles: &[Box<dyn PenroseTile>], transform: &RigidTransform, scale: f64,
+                                style: &Style) -> std::io::Result<()> {
+    std::fs::write(path, write_svg(tiles, transform, scale, style))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+
+    fn count(svg: &str, element: &str) -> usize {
+        svg.matches(&format!("<{} ", element)).count()
+    }
+
+    #[test]
+    fn test_svg_elements() {
+        let tiles = sun();
+        let mut style = Style::default();
+        let svg = write_svg(&tiles, &RigidTransform::identity(), 50., &style);
+        assert!(svg.starts_with("<?xml") && svg.ends_with("</svg>\n"));
+        assert_eq!(count(&svg, "polygon"), 5);
+        assert_eq!(count(&svg, "path"), 10);
+        assert_eq!(count(&svg, "line"), 0);
+        assert!(svg.contains("fill=\"#6495ed\"") && svg.contains("fill=\"#faf0e6\""));
+        assert!(svg.contains("stroke=\"#e6e6fa\" stroke-width=\"2\""));
+
+        style.show_arcs = false;
+        style.show_bars = true;
+        style.edge_weight = 0.;
+        style.background = None;
+        let svg = write_svg(&tiles, &RigidTransform::identity(), 50., &style);
+        assert_eq!(count(&svg, "path"), 0);
+        assert_eq!(count(&svg, "line"), 15);
+        assert_eq!(count(&svg, "rect"), 0);
+        assert!(svg.contains("<g stroke=\"none\">"));
+
+        // Triangles have arcs but no bars.
+        let svg = write_svg(&crate::convert::to_triangles(&tiles), &RigidTransform::identity(), 50., &style);
+        assert_eq!(count(&svg, "polygon"), 10);
+        assert_eq!(count(&svg, "line"), 0);
+    }
+
+    #[test]
+    fn test_svg_frame() {
+        // The patch fits inside the margin, the right way up.
+        let bounds = BoundingBox::enclosing(&sun()).unwrap();
+        let frame = Frame { transform: RigidTransform::identity(), min: bounds.min, max: bounds.max, scale: 100. };
+        let (w, h) = frame.size();
+        assert!((w - 100. * (bounds.max.0 - bounds.min.0) - 2. * SVG_MARGIN).abs() < 1e-9);
+        let top_left = frame.point((bounds.min.0, bounds.max.1));
+        assert!((top_left.0 - SVG_MARGIN).abs() < 1e-9 && (top_left.1 - SVG_MARGIN).abs() < 1e-9);
+        let bottom_right = frame.point((bounds.max.0, bounds.min.1));
+        assert!((bottom_right.0 - w + SVG_MARGIN).abs() < 1e-9 && (bottom_right.1 - h + SVG_MARGIN).abs() < 1e-9);
+
+        // A quarter turn anticlockwise from (1, 0) to (0, 1) on the page.
+        let frame = Frame { transform: RigidTransform::identity(), min: (0., 0.), max: (0., 0.), scale: 10. };
+        let arc = Arc { center: (0., 0.), radius: 1., start_angle: 0, end_angle: 90 };
+        assert_eq!(frame.arc(&arc), "M 20.000 10.000 A 10.000 10.000 0 0 0 10.000 0.000");
+        let arc = Arc { center: (0., 0.), radius: 1., start_angle: 270, end_angle: 180 };
+        assert!(frame.arc(&arc).contains(" 0 1 0 "));
+
+        // Turned a quarter, the same arc runs from (0, 1) to (-1, 0).
+        let frame = Frame { transform: RigidTransform::rotation(90.), ..frame };
+        let arc = Arc { center: (0., 0.), radius: 1., start_angle: 0, end_angle: 90 };
+        assert_eq!(frame.arc(&arc), "M 10.000 0.000 A 10.000 10.000 0 0 0 0.000 10.000");
+
+        // Mirrored in the y axis, it runs clockwise from (-1, 0) to (0, 1).
+        let frame = Frame { transform: RigidTransform::reflection(90.), ..frame };
+        assert_eq!(frame.arc(&arc), "M 0.000 10.000 A 10.000 10.000 0 0 1 10.000 0.000");
+    }
+
+    #[test]
+    fn test_svg_turned() {
+        let size = |rotation: f64| -> (f64, f64) {
+            let svg = write_svg(&sun(), &RigidTransform::rotation(rotation), 100., &Style::default());
+            let value = |key: &str| -> f64 {
+                let start = svg.find(&format!("{}=\"", key)).unwrap() + key.len() + 2;
+                svg[start..].split('"').next().unwrap().parse().unwrap()
+            };
+            (value("width"), value("height"))
+        };
+        // A sun looks the same turned by a fifth, and on its side a quarter
+        // turn swaps its width and height.
+        let (w, h) = size(0.);
+        assert!((w - h).abs() > 1.);
+        let (w1, h1) = size(72.);
+        assert!((w1 - w).abs() < 0.01 && (h1 - h).abs() < 0.01);
+        let (w2, h2) = size(90.);
+        assert!((w2 - h).abs() < 0.01 && (h2 - w).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_no_tiles() {
+        let svg = write_svg(&[], &RigidTransform::identity(), 10., &Style::default());
+        assert!(svg.contains("width=\"20.000\" height=\"20.000\""));
+        assert_eq!(count(&svg, "polygon"), 0);
+    }
+}
@@ -0,0 +1,2529 @@
+use crate::exact::{Cyclotomic, ExactPoint};
+
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum EdgeLength {
+    SHORT,
+    LONG
+}
+
+/// The matching-rule marking at one end of an edge.
+///
+/// Kites, darts and Robinson triangles colour their vertices `H` or `T`.
+/// Rhombus edges are crossed by one arc, and carry `BigArc` or `SmallArc` at
+/// the end the crossing is nearest and `Plain` at the other.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum Mark {
+    H,
+    T,
+    BigArc,
+    SmallArc,
+    Plain,
+}
+
+/// The start and end of an edge.
+pub type Segment = ((f64, f64), (f64, f64));
+
+#[derive(Clone)]
+pub struct Edge {
+    pub center: (f64, f64),
+    pub angle: i32,
+    pub length: EdgeLength,
+    /// The marks at the start and end of the edge, which runs in the
+    /// direction of `angle`.
+    pub marks: (Mark, Mark),
+}
+
+impl Edge {
+    /// The start and end of the edge.
+    pub fn end_points(&self) -> Segment {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let half = match self.length {
+            EdgeLength::SHORT => phi / 2.,
+            EdgeLength::LONG => phi * phi / 2.,
+        };
+        let a = (self.angle as f64).to_radians();
+        let (dx, dy) = (half * a.cos(), half * a.sin());
+        ((self.center.0 - dx, self.center.1 - dy), (self.center.0 + dx, self.center.1 + dy))
+    }
+}
+
+/// Which way a tile faces: turned `36 * index()` degrees anticlockwise from
+/// its usual position.
+///
+/// Tiles only ever meet at multiples of 36 degrees, so their orientations
+/// are kept as one of ten steps and compare exactly. A whole patch can still
+/// be turned by any angle with a `RigidTransform`.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone, Default)]
+pub struct Orientation(u8);
+
+impl Orientation {
+    pub fn new(index: i32) -> Self {
+        Orientation(index.rem_euclid(10) as u8)
+    }
+
+    /// The orientation nearest to `degrees`.
+    pub fn from_degrees(degrees: i32) -> Self {
+        Orientation::new((degrees.rem_euclid(360) + 18) / 36)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn degrees(&self) -> i32 {
+        36 * self.0 as i32
+    }
+
+    pub fn radians(&self) -> f64 {
+        (self.degrees() as f64).to_radians()
+    }
+
+    /// Turned by `degrees`, to the nearest step.
+    pub fn rotated(&self, degrees: i32) -> Self {
+        Orientation::from_degrees(self.degrees() + degrees)
+    }
+}
+
+/// An axis-aligned rectangle in tile coordinates.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct BoundingBox {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+impl BoundingBox {
+    pub fn new(min: (f64, f64), max: (f64, f64)) -> Self {
+        Self { min, max }
+    }
+
+    /// The square of half-width `r` centred on `c`.
+    pub fn around(c: (f64, f64), r: f64) -> Self {
+        Self { min: (c.0 - r, c.1 - r), max: (c.0 + r, c.1 + r) }
+    }
+
+    pub fn contains(&self, p: (f64, f64)) -> bool {
+        self.min.0 <= p.0 && p.0 <= self.max.0 && self.min.1 <= p.1 && p.1 <= self.max.1
+    }
+
+    /// The smallest box holding every vertex of `tiles`, or `None` if there
+    /// are no tiles.
+    pub fn enclosing(tiles: &[Box<dyn PenroseTile>]) -> Option<Self> {
+        Self::around_points(tiles.iter().flat_map(|t| t.vertices()))
+    }
+
+    /// The smallest box holding `points`, or `None` if there are none.
+    pub fn around_points(mut points: impl Iterator<Item = (f64, f64)>) -> Option<Self> {
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |b, p| {
+            Self::new((b.min.0.min(p.0), b.min.1.min(p.1)), (b.max.0.max(p.0), b.max.1.max(p.1)))
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct Arc {
+    pub center: (f64, f64),
+    pub radius: f64,
+    pub start_angle: i32,
+    pub end_angle: i32,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum Tile {
+    DART,
+    KITE,
+    FAT,
+    SKINNY,
+    ROBINSON_A,
+    ROBINSON_A_MIRROR,
+    ROBINSON_B,
+    ROBINSON_B_MIRROR,
+}
+
+impl Tile {
+    pub fn all() -> [Tile; 8] {
+        [Tile::DART, Tile::KITE, Tile::FAT, Tile::SKINNY,
+         Tile::ROBINSON_A, Tile::ROBINSON_A_MIRROR, Tile::ROBINSON_B, Tile::ROBINSON_B_MIRROR]
+    }
+
+    /// The name of the kind, as written in patch files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tile::DART => "DART",
+            Tile::KITE => "KITE",
+            Tile::FAT => "FAT",
+            Tile::SKINNY => "SKINNY",
+            Tile::ROBINSON_A => "ROBINSON_A",
+            Tile::ROBINSON_A_MIRROR => "ROBINSON_A_MIRROR",
+            Tile::ROBINSON_B => "ROBINSON_B",
+            Tile::ROBINSON_B_MIRROR => "ROBINSON_B_MIRROR",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Tile> {
+        Tile::all().iter().cloned().find(|t| t.name() == name)
+    }
+
+    /// True for the Robinson triangles, which have three edges.
+    pub fn is_triangle(&self) -> bool {
+        matches!(self, Tile::ROBINSON_A | Tile::ROBINSON_A_MIRROR | Tile::ROBINSON_B | Tile::ROBINSON_B_MIRROR)
+    }
+}
+
+/// Operations shared by every tile shape.
+///
+/// Edges are numbered 1 to n, where n is 4 for the kites, darts and rhombi
+/// and 3 for the Robinson triangles; edge `i` runs from vertex `i-1` to vertex
+/// `i%n` of `vertices()`.
+pub trait PenroseTile {
+    fn new(x: f64, y: f64, a: i32) -> Self where Self: Sized;
+
+    /// A tile whose centre is known exactly; see `exact_vertices`.
+    fn new_exact(p: ExactPoint, a: i32) -> Self where Self: Sized;
+
+    fn rotate(&self, angle: i32) -> Self where Self: Sized;
+    fn translate(&self, ox: f64, oy: f64) -> Self where Self: Sized;
+
+    fn kind(&self) -> Tile;
+    fn center(&self) -> (f64, f64);
+    fn orientation(&self) -> Orientation;
+
+    /// True for a kite, dart or rhombus that is the mirror image, in the line
+    /// through its centre at its angle, of the tile `new` builds. Mirrored
+    /// triangles are kinds of their own instead.
+    fn reflected(&self) -> bool {
+        false
+    }
+
+    /// The orientation in degrees.
+    fn angle(&self) -> i32 {
+        self.orientation().degrees()
+    }
+    fn exact_center(&self) -> Option<ExactPoint>;
+
+    /// Vertex positions in the plane, in edge order.
+    fn vertices(&self) -> Vec<(f64, f64)>;
+
+    /// Interior angle in degrees at each vertex of `vertices()`.
+    fn interior_angles(&self) -> Vec<i32>;
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32>;
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32>;
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32>;
+    fn get_big_arc(&self) -> Arc;
+    fn get_small_arc(&self) -> Arc;
+
+    /// The Ammann bar segments across the tile, as pairs of end points.
+    /// Bars are only drawn on whole tiles, so triangles have none.
+    fn get_ammann_bars(&self) -> Vec<(f64, f64)> {
+        Vec::new()
+    }
+
+    fn polygon(&self, xoff: f32, yoff: f32, scale: f32) -> Vec<(f32,f32)> {
+        let xoff64 = xoff as f64;
+        let yoff64 = yoff as f64;
+        let scale64 = scale as f64;
+
+        self.vertices().iter()
+            .map(|p| ((p.0*scale64 + xoff64) as f32, (p.1*scale64 + yoff64) as f32))
+            .collect()
+    }
+
+    fn edge_center(&self, e: i32) -> Result<(f64, f64), i32> {
+        let (p1, p2) = self.edge_points(e)?;
+        Ok(( (p1.0+p2.0)/2., (p1.1+p2.1)/2. ))
+    }
+
+    fn edge_points(&self, e: i32) -> Result<Segment, i32> {
+        let pts = self.vertices();
+        let (i1, i2) = edge_index_to_vertex_tuple(e, pts.len())?;
+        Ok(( pts[i1], pts[i2] ))
+    }
+
+    fn get_edges(&self) -> Vec<Edge> {
+        let mut result = Vec::new();
+        for i in 1..5 {
+            let mut e = Edge { center: (0., 0.), angle: 0, length: EdgeLength::SHORT, marks: (Mark::Plain, Mark::Plain) };
+            match self.edge_center(i) {
+                Ok(c) => e.center = c,
+                Err(_) => continue,
+            }
+            match self.edge_angle(i) {
+                Ok(a) => e.angle = a,
+                Err(_) => continue,
+            }
+            match self.edge_length(i) {
+                Ok(l) => e.length = l,
+                Err(_) => continue,
+            }
+            match self.edge_marks(i) {
+                Ok(m) => e.marks = m,
+                Err(_) => continue,
+            }
+            result.push(e);
+        }
+        result
+    }
+
+    fn translate_exact(&self, d: ExactPoint) -> Self where Self: Sized {
+        match self.exact_center() {
+            Some(c) => Self::new_exact(c + d, self.angle()),
+            None => {
+                let (ox, oy) = d.to_f64();
+                self.translate(ox, oy)
+            }
+        }
+    }
+
+    /// Exact vertex positions, in the same order as `vertices()`.
+    ///
+    /// Only available for tiles built with `new_exact` (or placed with one of
+    /// the `place_*_edge_exact` functions).
+    fn exact_vertices(&self) -> Option<Vec<ExactPoint>> {
+        let c = self.exact_center()?;
+        let mut local = exact_local_vertices(self.kind());
+        if self.reflected() {
+            let n = local.len();
+            local = (0..n).map(|i| local[(n - i) % n].conj()).collect();
+        }
+        local.iter()
+            .map(|p| p.rotate(self.angle()).map(|r| c + r))
+            .collect()
+    }
+
+    fn exact_edge_points(&self, e: i32) -> Option<(ExactPoint, ExactPoint)> {
+        let pts = self.exact_vertices()?;
+        let (i1, i2) = edge_index_to_vertex_tuple(e, pts.len()).ok()?;
+        Some(( pts[i1], pts[i2] ))
+    }
+
+    fn exact_edge_center(&self, e: i32) -> Option<ExactPoint> {
+        let (p1, p2) = self.exact_edge_points(e)?;
+        Some(p1.midpoint(&p2))
+    }
+}
+
+/// True if edge `e1` of `t1` and edge `e2` of `t2` are the same segment
+/// traversed in opposite directions, i.e. the two tiles are glued there.
+pub fn edges_match_exact(t1: &dyn PenroseTile, e1: i32, t2: &dyn PenroseTile, e2: i32) -> bool {
+    match (t1.exact_edge_points(e1), t2.exact_edge_points(e2)) {
+        (Some((a1, b1)), Some((a2, b2))) => a1 == b2 && b1 == a2,
+        _ => false,
+    }
+}
+
+// Tile-local vertices at angle 0, matching `vertices()`. Edges are phi or
+// phi^2 long, so every vertex is in Z[zeta] or half of it.
+pub(crate) fn exact_local_vertices(kind: Tile) -> Vec<ExactPoint> {
+    let z = |k| Cyclotomic::zeta_pow(k);
+    let phi = Cyclotomic::phi();
+    let one = Cyclotomic::ONE;
+    let p = ExactPoint::new;
+
+    match kind {
+        Tile::DART => vec![p(Cyclotomic::ZERO),
+                           p(phi * z(7)),
+                           p(phi),
+                           p(phi * z(3))],
+        Tile::KITE => vec![p(-phi),
+                           p(one - phi * z(2)),
+                           p(one),
+                           p(one + phi * z(3))],
+        Tile::FAT => {
+            let v0 = -p(phi * phi).half();
+            vec![v0,
+                 v0 + p(phi * z(9)),
+                 -v0,
+                 v0 + p(phi * z(1))]
+        }
+        Tile::SKINNY => {
+            let v0 = -p(phi * (one + z(1))).half();
+            vec![v0,
+                 v0 + p(phi),
+                 -v0,
+                 v0 + p(phi * z(1))]
+        }
+        // The triangles are halves of the kite and dart, in the same frame.
+        Tile::ROBINSON_A | Tile::ROBINSON_A_MIRROR | Tile::ROBINSON_B | Tile::ROBINSON_B_MIRROR => {
+            let (whole, mirror) = robinson_parent(kind);
+            let v = exact_local_vertices(whole);
+            if mirror { vec![v[0], v[2], v[3]] } else { vec![v[0], v[1], v[2]] }
+        }
+    }
+}
+
+pub fn new_tile(kind: Tile, x: f64, y: f64, angle: i32) -> Box<dyn PenroseTile> {
+    match kind {
+        Tile::DART => Box::new(Dart::new(x, y, angle)),
+        Tile::KITE => Box::new(Kite::new(x, y, angle)),
+        Tile::FAT => Box::new(Fat::new(x, y, angle)),
+        Tile::SKINNY => Box::new(Skinny::new(x, y, angle)),
+        Tile::ROBINSON_A => Box::new(RobinsonA::new(x, y, angle)),
+        Tile::ROBINSON_A_MIRROR => Box::new(RobinsonA::new(x, y, angle).mirror()),
+        Tile::ROBINSON_B => Box::new(RobinsonB::new(x, y, angle)),
+        Tile::ROBINSON_B_MIRROR => Box::new(RobinsonB::new(x, y, angle).mirror()),
+    }
+}
+
+pub fn new_exact_tile(kind: Tile, p: ExactPoint, angle: i32) -> Box<dyn PenroseTile> {
+    match kind {
+        Tile::DART => Box::new(Dart::new_exact(p, angle)),
+        Tile::KITE => Box::new(Kite::new_exact(p, angle)),
+        Tile::FAT => Box::new(Fat::new_exact(p, angle)),
+        Tile::SKINNY => Box::new(Skinny::new_exact(p, angle)),
+        Tile::ROBINSON_A => Box::new(RobinsonA::new_exact(p, angle)),
+        Tile::ROBINSON_A_MIRROR => Box::new(RobinsonA::new_exact(p, angle).mirror()),
+        Tile::ROBINSON_B => Box::new(RobinsonB::new_exact(p, angle)),
+        Tile::ROBINSON_B_MIRROR => Box::new(RobinsonB::new_exact(p, angle).mirror()),
+    }
+}
+
+pub fn copy_tile(t: &dyn PenroseTile) -> Box<dyn PenroseTile> {
+    let (x, y) = t.center();
+    let copy = match t.exact_center() {
+        Some(p) => new_exact_tile(t.kind(), p, t.angle()),
+        None => new_tile(t.kind(), x, y, t.angle()),
+    };
+    if t.reflected() { reflect_tile(copy.as_ref()) } else { copy }
+}
+
+/// The mirror image of `t` in the line through its centre at its angle. For
+/// a triangle that is the other half of the same kite or dart.
+pub fn reflect_tile(t: &dyn PenroseTile) -> Box<dyn PenroseTile> {
+    let (cx, cy) = t.center();
+    let (orientation, exact, reflected) = (t.orientation(), t.exact_center(), !t.reflected());
+    match t.kind() {
+        Tile::DART => Box::new(Dart { cx, cy, orientation, exact, reflected }),
+        Tile::KITE => Box::new(Kite { cx, cy, orientation, exact, reflected }),
+        Tile::FAT => Box::new(Fat { cx, cy, orientation, exact, reflected }),
+        Tile::SKINNY => Box::new(Skinny { cx, cy, orientation, exact, reflected }),
+        kind => {
+            let mirrored = kind == Tile::ROBINSON_A || kind == Tile::ROBINSON_B;
+            match robinson_parent(kind).0 {
+                Tile::KITE => Box::new(RobinsonA { cx, cy, orientation, exact, mirrored }),
+                _ => Box::new(RobinsonB { cx, cy, orientation, exact, mirrored }),
+            }
+        }
+    }
+}
+
+fn edge_index_to_vertex_tuple(e: i32, n: usize) -> Result<(usize, usize), i32> {
+    if e < 1 || e as usize > n {
+        return Err(e)
+    }
+    Ok(((e - 1) as usize, e as usize % n))
+}
+
+// The marks of edge `e` of a tile whose vertices are coloured `colours`.
+fn colour_marks(colours: &[Mark], e: i32) -> Result<(Mark, Mark), i32> {
+    let (i1, i2) = edge_index_to_vertex_tuple(e, colours.len())?;
+    Ok((colours[i1], colours[i2]))
+}
+
+// The point a fraction `t` of the way from `p1` to `p2`.
+fn interpolate(p1: (f64, f64), p2: (f64, f64), t: f64) -> (f64, f64) {
+    (p1.0 + t*(p2.0 - p1.0), p1.1 + t*(p2.1 - p1.1))
+}
+
+// A reflected tile is its unreflected twin `t` mirrored in the line through
+// its centre at its angle. Its vertices are listed from the mirror of vertex
+// 0 backwards, so they still run anticlockwise, and its edge `e` is the
+// mirror of `t`'s edge n+1-e, run the other way.
+fn mirrored_points(t: &dyn PenroseTile, pts: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    let (cx, cy) = t.center();
+    let (s, c) = (2. * t.angle() as f64).to_radians().sin_cos();
+    pts.iter()
+        .map(|p| (p.0 - cx, p.1 - cy))
+        .map(|(dx, dy)| (cx + c*dx + s*dy, cy + s*dx - c*dy))
+        .collect()
+}
+
+fn mirrored_order<T: Copy>(v: Vec<T>) -> Vec<T> {
+    let n = v.len();
+    (0..n).map(|i| v[(n - i) % n]).collect()
+}
+
+fn mirrored_vertices(t: &dyn PenroseTile) -> Vec<(f64, f64)> {
+    mirrored_order(mirrored_points(t, t.vertices()))
+}
+
+fn mirrored_interior_angles(t: &dyn PenroseTile) -> Vec<i32> {
+    mirrored_order(t.interior_angles())
+}
+
+fn mirrored_edge(t: &dyn PenroseTile, e: i32) -> Result<i32, i32> {
+    let n = t.interior_angles().len() as i32;
+    if e < 1 || e > n { Err(e) } else { Ok(n + 1 - e) }
+}
+
+fn mirrored_edge_angle(t: &dyn PenroseTile, e: i32) -> Result<i32, i32> {
+    let a = t.edge_angle(mirrored_edge(t, e)?)?;
+    Ok((2*t.angle() + 180 - a).rem_euclid(360))
+}
+
+fn mirrored_edge_length(t: &dyn PenroseTile, e: i32) -> Result<EdgeLength, i32> {
+    t.edge_length(mirrored_edge(t, e)?)
+}
+
+fn mirrored_edge_marks(t: &dyn PenroseTile, e: i32) -> Result<(Mark, Mark), i32> {
+    let (m1, m2) = t.edge_marks(mirrored_edge(t, e)?)?;
+    Ok((m2, m1))
+}
+
+// Arcs run anticlockwise, so the mirrored arc runs between the mirrored ends
+// the other way round.
+fn mirrored_arc(t: &dyn PenroseTile, a: Arc) -> Arc {
+    Arc {
+        center: mirrored_points(t, vec![a.center])[0],
+        radius: a.radius,
+        start_angle: (2*t.angle() - a.end_angle).rem_euclid(360),
+        end_angle: (2*t.angle() - a.start_angle).rem_euclid(360),
+    }
+}
+
+// Rotate the tile-local points by `angle` degrees and move them to (cx, cy).
+fn place_points(cx: f64, cy: f64, angle: i32, local: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let angle_in_radians = angle as f64 * std::f64::consts::PI / 180.;
+    let c = angle_in_radians.cos();
+    let s = angle_in_radians.sin();
+    local.iter()
+        .map(|p| (cx + c*p.0 - s*p.1, cy + s*p.0 + c*p.1))
+        .collect()
+}
+
+pub struct Dart {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub reflected: bool,
+}
+
+impl Dart {
+    /// The mirror image in the dart's axis of symmetry.
+    pub fn mirror(&self) -> Dart {
+        Dart { reflected: !self.reflected, ..*self }
+    }
+}
+
+impl PenroseTile for Dart {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            reflected: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            reflected: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> Dart {
+        Dart{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> Dart {
+        Dart{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile { Tile::DART }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn reflected(&self) -> bool { self.reflected }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_vertices(&self.mirror())
+        }
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let h = (5.+2.*s5).sqrt()/2.;
+
+        place_points(self.cx, self.cy, self.angle(),
+                     &[( 0.,  0.),
+                       (-0.5, -h),
+                       (phi,  0.),
+                       (-0.5,  h)])
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        if self.reflected {
+            return mirrored_interior_angles(&self.mirror())
+        }
+        vec![216, 36, 72, 36]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        if self.reflected {
+            return mirrored_edge_angle(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok((252 + self.angle())%360),
+            2 => Ok(( 36 + self.angle())%360),
+            3 => Ok((144 + self.angle())%360),
+            4 => Ok((288 + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        if self.reflected {
+            return mirrored_edge_length(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok(EdgeLength::SHORT),
+            2 => Ok(EdgeLength::LONG),
+            3 => Ok(EdgeLength::LONG),
+            4 => Ok(EdgeLength::SHORT),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.reflected {
+            return mirrored_edge_marks(&self.mirror(), e)
+        }
+        colour_marks(&[Mark::T, Mark::H, Mark::T, Mark::H], e)
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_big_arc())
+        }
+        let pts = self.vertices();
+        Arc {
+            center: pts[2],
+            radius: 0.75,
+            start_angle: (self.angle() + 144)%360,
+            end_angle: (self.angle() + 216)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_small_arc())
+        }
+        let pts = self.vertices();
+        Arc {
+            center: pts[0],
+            radius: 0.6,
+            start_angle: (self.angle() + 252)%360,
+            end_angle: (self.angle() + 468)%360,
+        }
+    }
+
+    fn get_ammann_bars(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_points(&self.mirror(), self.mirror().get_ammann_bars())
+        }
+        let s5 = 5_f64.sqrt();
+        let t1 = 1. / 4.;
+        let t2 = 1. / (3. + s5);
+        let t3 = (1. + s5) / 4.;
+
+        let p = self.vertices();
+        let a0 = interpolate(p[1], p[0], t3);
+        let a1 = interpolate(p[1], p[0], t1);
+        let a2 = interpolate(p[1], p[2], t2);
+        let a3 = interpolate(p[3], p[2], t2);
+        let a4 = interpolate(p[3], p[0], t1);
+        let a5 = interpolate(p[3], p[0], t3);
+
+        vec![a1, a2, a2, a0, a5, a3, a3, a4]
+    }
+}
+
+pub fn place_dart_edge(e: i32, pt: (f64,f64), edge_angle: i32) -> Dart {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+    let h = (5.+2.*s5).sqrt()/2.;
+
+    match e {
+        1 => {
+            let new_angle = (edge_angle + 288) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = 0.25;
+            let dy = h/2.;
+            Dart::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        2 => {
+            let new_angle = (edge_angle + 144) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = 0.25 - phi/2.;
+            let dy = h/2.;
+            Dart::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        3 => {
+            let new_angle = (edge_angle + 36) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = 0.25 - phi/2.;
+            let dy = -h/2.;
+            Dart::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        4 => {
+            let new_angle = (edge_angle + 252) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = 0.25;
+            let dy = -h/2.;
+            Dart::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        _ => {
+            Dart::new(0.,0.,0)
+        }
+    }
+}
+
+pub struct Kite {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub reflected: bool,
+}
+
+impl Kite {
+    /// The mirror image in the kite's axis of symmetry.
+    pub fn mirror(&self) -> Kite {
+        Kite { reflected: !self.reflected, ..*self }
+    }
+}
+
+impl PenroseTile for Kite {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            reflected: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            reflected: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> Kite {
+        Kite{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> Kite {
+        Kite{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile { Tile::KITE }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn reflected(&self) -> bool { self.reflected }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_vertices(&self.mirror())
+        }
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let h = (5.+2.*s5).sqrt()/2.;
+
+        place_points(self.cx, self.cy, self.angle(),
+                     &[(-phi, 0.),
+                       ( 0.5, -h),
+                       ( 1.,  0.),
+                       ( 0.5,  h)])
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        if self.reflected {
+            return mirrored_interior_angles(&self.mirror())
+        }
+        vec![72, 72, 144, 72]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        if self.reflected {
+            return mirrored_edge_angle(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok((324 + self.angle())%360),
+            2 => Ok(( 72 + self.angle())%360),
+            3 => Ok((108 + self.angle())%360),
+            4 => Ok((216 + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        if self.reflected {
+            return mirrored_edge_length(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok(EdgeLength::LONG),
+            2 => Ok(EdgeLength::SHORT),
+            3 => Ok(EdgeLength::SHORT),
+            4 => Ok(EdgeLength::LONG),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.reflected {
+            return mirrored_edge_marks(&self.mirror(), e)
+        }
+        colour_marks(&[Mark::H, Mark::T, Mark::H, Mark::T], e)
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_big_arc())
+        }
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let pts = self.vertices();
+        Arc {
+            center: pts[0],
+            radius: 1.0 + phi - 0.75,
+            start_angle: (self.angle() + 324)%360,
+            end_angle: (self.angle() + 396)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_small_arc())
+        }
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let pts = self.vertices();
+        Arc {
+            center: pts[2],
+            radius: phi - 0.6,
+            start_angle: (self.angle() + 108)%360,
+            end_angle: (self.angle() + 252)%360,
+        }
+    }
+
+    fn get_ammann_bars(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_points(&self.mirror(), self.mirror().get_ammann_bars())
+        }
+        let s5 = 5_f64.sqrt();
+        let t1 = 1. / 4.;
+        let t2 = 1. / (3. + s5);
+
+        let p = self.vertices();
+        let r0 = interpolate(p[0], p[1], t2);
+        let r3 = interpolate(p[2], p[1], t1);
+        let r4 = interpolate(p[2], p[3], t1);
+        let r7 = interpolate(p[0], p[3], t2);
+
+        vec![r3, r7, r7, r0, r0, r4]
+    }
+}
+
+pub fn place_kite_edge(e: i32, pt: (f64,f64), edge_angle: i32) -> Kite {
+    let s5 = 5_f64.sqrt();
+    let phi = (1.+s5)/2.;
+    let h = (5.+2.*s5).sqrt()/2.;
+
+    match e {
+        1 => {
+            let new_angle = (edge_angle + 216) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = phi/2. - 0.25;
+            let dy = h/2.;
+            Kite::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        2 => {
+            let new_angle = (edge_angle + 360 - 252) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = -0.75;
+            let dy = h/2.;
+            Kite::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        3 => {
+            let new_angle = (edge_angle + 72) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = -0.75;
+            let dy = -h/2.;
+            Kite::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        4 => {
+            let new_angle = (edge_angle + 360 - 36) % 360;
+            let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+            let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+
+            let dx = phi/2. - 0.25;
+            let dy = -h/2.;
+            Kite::new(pt.0 + dx*c - dy*s,
+                      pt.1 + dx*s + dy*c,
+                      new_angle)
+        }
+        _ => {
+            Kite::new(0.,0.,0)
+        }
+    }
+}
+
+pub struct Fat {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub reflected: bool,
+}
+
+impl Fat {
+    /// The mirror image in the fat rhombus's long diagonal.
+    pub fn mirror(&self) -> Fat {
+        Fat { reflected: !self.reflected, ..*self }
+    }
+}
+
+impl PenroseTile for Fat {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            reflected: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            reflected: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> Fat {
+        Fat{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> Fat {
+        Fat{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile { Tile::FAT }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn reflected(&self) -> bool { self.reflected }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_vertices(&self.mirror())
+        }
+        let s5 = 5_f64.sqrt();
+        let x = (3. + s5) / 4.;
+        let y = (10. + 2. * s5).sqrt() / 4.;
+
+        place_points(self.cx, self.cy, self.angle(),
+                     &[(-x, 0.),
+                       (0., -y),
+                       ( x, 0.),
+                       (0.,  y)])
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        if self.reflected {
+            return mirrored_interior_angles(&self.mirror())
+        }
+        vec![72, 108, 72, 108]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        if self.reflected {
+            return mirrored_edge_angle(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok((324 + self.angle())%360),
+            2 => Ok(( 36 + self.angle())%360),
+            3 => Ok((144 + self.angle())%360),
+            4 => Ok((216 + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        if self.reflected {
+            return mirrored_edge_length(&self.mirror(), e)
+        }
+        match e {
+            1..=4 => Ok(EdgeLength::SHORT),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.reflected {
+            return mirrored_edge_marks(&self.mirror(), e)
+        }
+        // The big arc about vertex 0 crosses edges 1 and 4 nearest their
+        // other ends; the small arc about vertex 2 crosses edges 2 and 3
+        // nearest vertex 2.
+        match e {
+            1 => Ok((Mark::Plain, Mark::BigArc)),
+            2 => Ok((Mark::Plain, Mark::SmallArc)),
+            3 => Ok((Mark::SmallArc, Mark::Plain)),
+            4 => Ok((Mark::BigArc, Mark::Plain)),
+            _ => Err(e),
+        }
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_big_arc())
+        }
+        let pts = self.vertices();
+        Arc {
+            center: pts[0],
+            radius: 1.25,
+            start_angle: (self.angle() + 324)%360,
+            end_angle: (self.angle() + 36)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_small_arc())
+        }
+        let pts = self.vertices();
+        Arc {
+            center: pts[2],
+            radius: 0.375,
+            start_angle: (self.angle() + 144)%360,
+            end_angle: (self.angle() + 216)%360,
+        }
+    }
+
+    fn get_ammann_bars(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_points(&self.mirror(), self.mirror().get_ammann_bars())
+        }
+        let s5 = 5_f64.sqrt();
+        let t1 = 1. / 4.;
+        let t3 = (1. + s5) / 4.;
+
+        let p = self.vertices();
+        let a0 = interpolate(p[2], p[1], t3);
+        let a1 = interpolate(p[2], p[1], t1);
+        let a2 = interpolate(p[2], p[3], t1);
+        let a3 = interpolate(p[2], p[3], t3);
+
+        vec![a0, a2, a1, a3]
+    }
+}
+
+pub struct Skinny {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub reflected: bool,
+}
+
+impl Skinny {
+    /// The mirror image in the line through the centre at the tile's angle.
+    /// That isn't a diagonal, so the outline turns by 36 degrees as well.
+    pub fn mirror(&self) -> Skinny {
+        Skinny { reflected: !self.reflected, ..*self }
+    }
+}
+
+impl PenroseTile for Skinny {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            reflected: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            reflected: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> Skinny {
+        Skinny{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> Skinny {
+        Skinny{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile { Tile::SKINNY }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn reflected(&self) -> bool { self.reflected }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_vertices(&self.mirror())
+        }
+        let s5 = 5_f64.sqrt();
+        let x2 = (s5 - 1.0) / 8.0;
+        let x3 = (5.0 + 3.0 * s5) / 8.0;
+        let y2 = (std::f64::consts::PI / 5.0).cos() * (std::f64::consts::PI / 5.0).sin();
+
+        place_points(self.cx, self.cy, self.angle(),
+                     &[(-x3, -y2),
+                       ( x2, -y2),
+                       ( x3,  y2),
+                       (-x2,  y2)])
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        if self.reflected {
+            return mirrored_interior_angles(&self.mirror())
+        }
+        vec![36, 144, 36, 144]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        if self.reflected {
+            return mirrored_edge_angle(&self.mirror(), e)
+        }
+        match e {
+            1 => Ok((360 + self.angle())%360),
+            2 => Ok(( 36 + self.angle())%360),
+            3 => Ok((180 + self.angle())%360),
+            4 => Ok((216 + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        if self.reflected {
+            return mirrored_edge_length(&self.mirror(), e)
+        }
+        match e {
+            1..=4 => Ok(EdgeLength::SHORT),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.reflected {
+            return mirrored_edge_marks(&self.mirror(), e)
+        }
+        // The arcs about vertices 1 and 3 are short, so they cross the
+        // edges nearest their centres.
+        match e {
+            1 => Ok((Mark::Plain, Mark::BigArc)),
+            2 => Ok((Mark::BigArc, Mark::Plain)),
+            3 => Ok((Mark::Plain, Mark::SmallArc)),
+            4 => Ok((Mark::SmallArc, Mark::Plain)),
+            _ => Err(e),
+        }
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_big_arc())
+        }
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let pts = self.vertices();
+        Arc {
+            center: pts[1],
+            radius: phi - 1.25,
+            start_angle: (self.angle() + 36)%360,
+            end_angle: (self.angle() + 180)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        if self.reflected {
+            return mirrored_arc(&self.mirror(), self.mirror().get_small_arc())
+        }
+        let pts = self.vertices();
+        Arc {
+            center: pts[3],
+            radius: 0.375,
+            start_angle: (self.angle() + 216)%360,
+            end_angle: self.angle(),
+        }
+    }
+
+    fn get_ammann_bars(&self) -> Vec<(f64, f64)> {
+        if self.reflected {
+            return mirrored_points(&self.mirror(), self.mirror().get_ammann_bars())
+        }
+        let s5 = 5_f64.sqrt();
+        let t1 = 1. / 4.;
+        let x2 = (s5 - 1.0) / 8.0;
+        let x3 = (5.0 + 3.0 * s5) / 8.0;
+        let t4 = (1. - t1) * (x3 + x2) / (x3 - x2);
+
+        let p = self.vertices();
+        let a0 = interpolate(p[3], p[0], t1);
+        let a1 = interpolate(p[0], p[1], t4);
+        let a2 = interpolate(p[2], p[1], t4);
+        let a3 = interpolate(p[3], p[2], t1);
+
+        vec![a0, a1, a2, a3]
+    }
+}
+
+
+// Centre and angle that put edge `e` of a `kind` tile on the edge whose
+// midpoint is `pt` and whose direction is `edge_angle`, traversed backwards.
+fn edge_placement(kind: Tile, e: i32, pt: (f64,f64), edge_angle: i32) -> Result<(f64, f64, i32), i32> {
+    let local = new_tile(kind, 0., 0., 0);
+    let (p1, p2) = local.edge_points(e)?;
+    let new_angle = (edge_angle + 180 - local.edge_angle(e)?).rem_euclid(360);
+
+    let c = ((new_angle as f64) * std::f64::consts::PI / 180.).cos();
+    let s = ((new_angle as f64) * std::f64::consts::PI / 180.).sin();
+    let dx = (p1.0 + p2.0) / 2.;
+    let dy = (p1.1 + p2.1) / 2.;
+    Ok((pt.0 - (dx*c - dy*s), pt.1 - (dx*s + dy*c), new_angle))
+}
+
+pub fn place_fat_edge(e: i32, pt: (f64,f64), edge_angle: i32) -> Fat {
+    match edge_placement(Tile::FAT, e, pt, edge_angle) {
+        Ok((x, y, a)) => Fat::new(x, y, a),
+        Err(_) => Fat::new(0.,0.,0),
+    }
+}
+
+pub fn place_skinny_edge(e: i32, pt: (f64,f64), edge_angle: i32) -> Skinny {
+    match edge_placement(Tile::SKINNY, e, pt, edge_angle) {
+        Ok((x, y, a)) => Skinny::new(x, y, a),
+        Err(_) => Skinny::new(0.,0.,0),
+    }
+}
+
+/// A tile of any kind with its edge `e` on the edge whose midpoint is `pt`
+/// and whose direction is `edge_angle`, like `place_dart_edge`.
+pub fn place_edge(kind: Tile, e: i32, pt: (f64,f64), edge_angle: i32) -> Box<dyn PenroseTile> {
+    match kind {
+        Tile::DART => Box::new(place_dart_edge(e, pt, edge_angle)),
+        Tile::KITE => Box::new(place_kite_edge(e, pt, edge_angle)),
+        Tile::FAT => Box::new(place_fat_edge(e, pt, edge_angle)),
+        Tile::SKINNY => Box::new(place_skinny_edge(e, pt, edge_angle)),
+        _ => match edge_placement(kind, e, pt, edge_angle) {
+            Ok((x, y, a)) => new_tile(kind, x, y, a),
+            Err(_) => new_tile(kind, 0., 0., 0),
+        },
+    }
+}
+
+// The kite or dart a Robinson triangle is half of, and whether it is the
+// mirrored half.
+pub(crate) fn robinson_parent(kind: Tile) -> (Tile, bool) {
+    match kind {
+        Tile::ROBINSON_A => (Tile::KITE, false),
+        Tile::ROBINSON_A_MIRROR => (Tile::KITE, true),
+        Tile::ROBINSON_B => (Tile::DART, false),
+        Tile::ROBINSON_B_MIRROR => (Tile::DART, true),
+        _ => (kind, false),
+    }
+}
+
+/// The acute Robinson triangle (36, 72, 72 degrees): half of a kite, cut
+/// along its axis.
+///
+/// The triangle uses the kite's frame, so a kite is a `RobinsonA` and its
+/// mirror image with the same centre and angle. The unmirrored half holds
+/// the kite's vertices 0, 1 and 2, the mirrored half vertices 0, 2 and 3.
+pub struct RobinsonA {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub mirrored: bool,
+}
+
+impl RobinsonA {
+    /// The other half of the same kite.
+    pub fn mirror(&self) -> RobinsonA {
+        RobinsonA { mirrored: !self.mirrored, ..*self }
+    }
+}
+
+impl PenroseTile for RobinsonA {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            mirrored: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            mirrored: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> RobinsonA {
+        RobinsonA{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> RobinsonA {
+        RobinsonA{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile {
+        if self.mirrored { Tile::ROBINSON_A_MIRROR } else { Tile::ROBINSON_A }
+    }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let h = (5.+2.*s5).sqrt()/2.;
+
+        let side = if self.mirrored { (0.5, h) } else { (0.5, -h) };
+        let pts = if self.mirrored { [(-phi, 0.), (1., 0.), side] } else { [(-phi, 0.), side, (1., 0.)] };
+        place_points(self.cx, self.cy, self.angle(), &pts)
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        vec![36, 72, 72]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        let angles = if self.mirrored { [0, 108, 216] } else { [324, 72, 180] };
+        match e {
+            1..=3 => Ok((angles[e as usize - 1] + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        match e {
+            1 => Ok(EdgeLength::LONG),
+            2 => Ok(EdgeLength::SHORT),
+            3 => Ok(EdgeLength::LONG),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.mirrored {
+            colour_marks(&[Mark::H, Mark::H, Mark::T], e)
+        } else {
+            colour_marks(&[Mark::H, Mark::T, Mark::H], e)
+        }
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let pts = self.vertices();
+        let (start, end) = if self.mirrored { (0, 36) } else { (324, 360) };
+        Arc {
+            center: pts[0],
+            radius: 1.0 + phi - 0.75,
+            start_angle: (self.angle() + start)%360,
+            end_angle: (self.angle() + end)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let pts = self.vertices();
+        let (head, start, end) = if self.mirrored { (1, 108, 180) } else { (2, 180, 252) };
+        Arc {
+            center: pts[head],
+            radius: phi - 0.6,
+            start_angle: (self.angle() + start)%360,
+            end_angle: (self.angle() + end)%360,
+        }
+    }
+}
+
+/// The obtuse Robinson triangle (108, 36, 36 degrees): half of a dart, cut
+/// along its axis.
+///
+/// As with `RobinsonA`, the triangle uses the dart's frame; the unmirrored
+/// half holds the dart's vertices 0, 1 and 2.
+pub struct RobinsonB {
+    pub cx: f64,
+    pub cy: f64,
+    pub orientation: Orientation,
+    pub exact: Option<ExactPoint>,
+    pub mirrored: bool,
+}
+
+impl RobinsonB {
+    /// The other half of the same dart.
+    pub fn mirror(&self) -> RobinsonB {
+        RobinsonB { mirrored: !self.mirrored, ..*self }
+    }
+}
+
+impl PenroseTile for RobinsonB {
+    fn new(x: f64, y: f64, a: i32) -> Self {
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: None,
+            mirrored: false
+        }
+    }
+
+    fn new_exact(p: ExactPoint, a: i32) -> Self {
+        let (x, y) = p.to_f64();
+        Self {
+            cx: x,
+            cy: y,
+            orientation: Orientation::from_degrees(a),
+            exact: Some(p),
+            mirrored: false
+        }
+    }
+
+    fn rotate(&self, angle: i32) -> RobinsonB {
+        RobinsonB{ orientation: self.orientation.rotated(angle), ..*self }
+    }
+
+    fn translate(&self, ox: f64, oy: f64) -> RobinsonB {
+        RobinsonB{ cx: (self.cx + ox), cy: (self.cy + oy), exact: None, ..*self }
+    }
+
+    fn kind(&self) -> Tile {
+        if self.mirrored { Tile::ROBINSON_B_MIRROR } else { Tile::ROBINSON_B }
+    }
+
+    fn center(&self) -> (f64, f64) { (self.cx, self.cy) }
+
+    fn orientation(&self) -> Orientation { self.orientation }
+
+    fn exact_center(&self) -> Option<ExactPoint> { self.exact }
+
+    fn vertices(&self) -> Vec<(f64, f64)> {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let h = (5.+2.*s5).sqrt()/2.;
+
+        let side = if self.mirrored { (-0.5, h) } else { (-0.5, -h) };
+        let pts = if self.mirrored { [(0., 0.), (phi, 0.), side] } else { [(0., 0.), side, (phi, 0.)] };
+        place_points(self.cx, self.cy, self.angle(), &pts)
+    }
+
+    fn interior_angles(&self) -> Vec<i32> {
+        vec![108, 36, 36]
+    }
+
+    fn edge_angle(&self, e: i32) -> Result<i32, i32> {
+        let angles = if self.mirrored { [0, 144, 288] } else { [252, 36, 180] };
+        match e {
+            1..=3 => Ok((angles[e as usize - 1] + self.angle())%360),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_length(&self, e: i32) -> Result<EdgeLength, i32> {
+        match e {
+            1 => Ok(EdgeLength::SHORT),
+            2 => Ok(EdgeLength::LONG),
+            3 => Ok(EdgeLength::SHORT),
+            _ => Err(e),
+        }
+    }
+
+    fn edge_marks(&self, e: i32) -> Result<(Mark, Mark), i32> {
+        if self.mirrored {
+            colour_marks(&[Mark::T, Mark::T, Mark::H], e)
+        } else {
+            colour_marks(&[Mark::T, Mark::H, Mark::T], e)
+        }
+    }
+
+    fn get_big_arc(&self) -> Arc {
+        let pts = self.vertices();
+        let (tip, start, end) = if self.mirrored { (1, 144, 180) } else { (2, 180, 216) };
+        Arc {
+            center: pts[tip],
+            radius: 0.75,
+            start_angle: (self.angle() + start)%360,
+            end_angle: (self.angle() + end)%360,
+        }
+    }
+
+    fn get_small_arc(&self) -> Arc {
+        let pts = self.vertices();
+        let (start, end) = if self.mirrored { (0, 108) } else { (252, 360) };
+        Arc {
+            center: pts[0],
+            radius: 0.6,
+            start_angle: (self.angle() + start)%360,
+            end_angle: (self.angle() + end)%360,
+        }
+    }
+}
+
+// Centre and angle that put edge `e` of a `kind` tile on the edge whose
+// midpoint is `pt` and whose direction is `edge_angle`, traversed backwards.
+fn exact_edge_placement(kind: Tile, e: i32, pt: ExactPoint, edge_angle: i32) -> Option<(ExactPoint, i32)> {
+    let local = exact_local_vertices(kind);
+    let (i1, i2) = edge_index_to_vertex_tuple(e, local.len()).ok()?;
+    let local_angle = new_tile(kind, 0., 0., 0).edge_angle(e).ok()?;
+    let new_angle = (edge_angle + 180 - local_angle).rem_euclid(360);
+
+    let mid = local[i1].midpoint(&local[i2]).rotate(new_angle)?;
+    Some((pt - mid, new_angle))
+}
+
+pub fn place_dart_edge_exact(e: i32, pt: ExactPoint, edge_angle: i32) -> Option<Dart> {
+    let (c, a) = exact_edge_placement(Tile::DART, e, pt, edge_angle)?;
+    Some(Dart::new_exact(c, a))
+}
+
+pub fn place_kite_edge_exact(e: i32, pt: ExactPoint, edge_angle: i32) -> Option<Kite> {
+    let (c, a) = exact_edge_placement(Tile::KITE, e, pt, edge_angle)?;
+    Some(Kite::new_exact(c, a))
+}
+
+pub fn place_fat_edge_exact(e: i32, pt: ExactPoint, edge_angle: i32) -> Option<Fat> {
+    let (c, a) = exact_edge_placement(Tile::FAT, e, pt, edge_angle)?;
+    Some(Fat::new_exact(c, a))
+}
+
+pub fn place_skinny_edge_exact(e: i32, pt: ExactPoint, edge_angle: i32) -> Option<Skinny> {
+    let (c, a) = exact_edge_placement(Tile::SKINNY, e, pt, edge_angle)?;
+    Some(Skinny::new_exact(c, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn angle_func(p: ((f64, f64), (f64, f64))) -> i32 {
+        let dx = p.1.0 - p.0.0;
+        let dy = p.1.1 - p.0.1;
+        (360 + (dy.atan2(dx) * 180. / std::f64::consts::PI) as i32) % 360
+    }
+
+    fn midpt_func(p: ((f64, f64), (f64, f64))) -> (f64,f64) {
+        ((p.0.0 + p.1.0) / 2., (p.0.1 + p.1.1) / 2.)
+    }
+
+    fn dist_func(p1: (f64,f64), p2: (f64,f64)) -> f64 {
+        let dx = p2.0 - p1.0;
+        let dy = p2.1 - p1.1;
+        (dx*dx + dy*dy).sqrt()
+    }
+
+    // Dart tests
+    #[test]
+    fn test_dart_new() {
+        let d1 = Dart::new(0., 0., 0);
+        assert_eq!(d1.cx, 0.);
+        assert_eq!(d1.cy, 0.);
+        assert_eq!(d1.angle(), 0);
+
+        // Angles go to the nearest multiple of 36.
+        let d2 = Dart::new(0., 0.,-40);
+        assert_eq!(d2.cx, 0.);
+        assert_eq!(d2.cy, 0.);
+        assert_eq!(d2.angle(),324);
+        assert_eq!(d2.orientation().index(), 9);
+
+        let d3 = Dart::new(0., 0.,400);
+        assert_eq!(d3.cx, 0.);
+        assert_eq!(d3.cy, 0.);
+        assert_eq!(d3.angle(), 36);
+    }
+
+    #[test]
+    fn test_dart_angle_internal() {
+        let internal_angles = [36, 72, 36, 216];
+        let d = Dart::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!((d.edge_angle(1)? + (540 - internal_angles[0]))%360, d.edge_angle(2)?);
+            assert_eq!((d.edge_angle(2)? + (540 - internal_angles[1]))%360, d.edge_angle(3)?);
+            assert_eq!((d.edge_angle(3)? + (540 - internal_angles[2]))%360, d.edge_angle(4)?);
+            assert_eq!((d.edge_angle(4)? + (540 - internal_angles[3]))%360, d.edge_angle(1)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_angle_0() {
+        let d = Dart::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_angle(1)?, 252);
+            assert_eq!(d.edge_angle(2)?,  36);
+            assert_eq!(d.edge_angle(3)?, 144);
+            assert_eq!(d.edge_angle(4)?, 288);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_angle_108() {
+        let d = Dart::new(0.,0.,108);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_angle(1)?,   0);
+            assert_eq!(d.edge_angle(2)?, 144);
+            assert_eq!(d.edge_angle(3)?, 252);
+            assert_eq!(d.edge_angle(4)?,  36);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // -252 should be the same as +108
+    #[test]
+    fn test_dart_angle_m252() {
+        let d = Dart::new(0.,0.,-252);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_angle(1)?,   0);
+            assert_eq!(d.edge_angle(2)?, 144);
+            assert_eq!(d.edge_angle(3)?, 252);
+            assert_eq!(d.edge_angle(4)?,  36);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_angle_180() {
+        let d = Dart::new(0.,0.,180);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_angle(1)?,  72);
+            assert_eq!(d.edge_angle(2)?, 216);
+            assert_eq!(d.edge_angle(3)?, 324);
+            assert_eq!(d.edge_angle(4)?, 108);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // -180 should be the same as +180
+    #[test]
+    fn test_dart_angle_m180() {
+        let d = Dart::new(0.,0.,-180);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_angle(1)?,  72);
+            assert_eq!(d.edge_angle(2)?, 216);
+            assert_eq!(d.edge_angle(3)?, 324);
+            assert_eq!(d.edge_angle(4)?, 108);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_edge_slopes() {
+        let d = Dart::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(angle_func(d.edge_points(1)?), d.edge_angle(1)?);
+            assert_eq!(angle_func(d.edge_points(2)?), d.edge_angle(2)?);
+            assert_eq!(angle_func(d.edge_points(3)?), d.edge_angle(3)?);
+            assert_eq!(angle_func(d.edge_points(4)?), d.edge_angle(4)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_edge_lengths() {
+        let d = Dart::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(d.edge_length(1)?, EdgeLength::SHORT);
+            assert_eq!(d.edge_length(2)?, EdgeLength::LONG);
+            assert_eq!(d.edge_length(3)?, EdgeLength::LONG);
+            assert_eq!(d.edge_length(4)?, EdgeLength::SHORT);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dart_edge_centers() {
+        let d = Dart::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(midpt_func(d.edge_points(1)?), d.edge_center(1)?);
+            assert_eq!(midpt_func(d.edge_points(2)?), d.edge_center(2)?);
+            assert_eq!(midpt_func(d.edge_points(3)?), d.edge_center(3)?);
+            assert_eq!(midpt_func(d.edge_points(4)?), d.edge_center(4)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // Kite tests
+    #[test]
+    fn test_kite_new() {
+        let k1 = Kite::new(0., 0., 0);
+        assert_eq!(k1.cx, 0.);
+        assert_eq!(k1.cy, 0.);
+        assert_eq!(k1.angle(), 0);
+
+        // Angles go to the nearest multiple of 36.
+        let k2 = Kite::new(0., 0.,-40);
+        assert_eq!(k2.cx, 0.);
+        assert_eq!(k2.cy, 0.);
+        assert_eq!(k2.angle(),324);
+        assert_eq!(k2.orientation().index(), 9);
+
+        let k3 = Kite::new(0., 0.,400);
+        assert_eq!(k3.cx, 0.);
+        assert_eq!(k3.cy, 0.);
+        assert_eq!(k3.angle(), 36);
+    }
+
+    #[test]
+    fn test_kite_angle_internal() {
+        let internal_angles = [72, 144, 72, 72];
+        let k = Kite::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!((k.edge_angle(1)? + (540 - internal_angles[0]))%360, k.edge_angle(2)?);
+            assert_eq!((k.edge_angle(2)? + (540 - internal_angles[1]))%360, k.edge_angle(3)?);
+            assert_eq!((k.edge_angle(3)? + (540 - internal_angles[2]))%360, k.edge_angle(4)?);
+            assert_eq!((k.edge_angle(4)? + (540 - internal_angles[3]))%360, k.edge_angle(1)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_angle_0() {
+        let k = Kite::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_angle(1)?, 324);
+            assert_eq!(k.edge_angle(2)?,  72);
+            assert_eq!(k.edge_angle(3)?, 108);
+            assert_eq!(k.edge_angle(4)?, 216);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_angle_108() {
+        let k = Kite::new(0.,0.,108);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_angle(1)?,  72);
+            assert_eq!(k.edge_angle(2)?, 180);
+            assert_eq!(k.edge_angle(3)?, 216);
+            assert_eq!(k.edge_angle(4)?, 324);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // -252 should be the same as +108
+    #[test]
+    fn test_kite_angle_m252() {
+        let k = Kite::new(0.,0.,-252);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_angle(1)?,  72);
+            assert_eq!(k.edge_angle(2)?, 180);
+            assert_eq!(k.edge_angle(3)?, 216);
+            assert_eq!(k.edge_angle(4)?, 324);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_angle_180() {
+        let k = Kite::new(0.,0.,180);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_angle(1)?, 144);
+            assert_eq!(k.edge_angle(2)?, 252);
+            assert_eq!(k.edge_angle(3)?, 288);
+            assert_eq!(k.edge_angle(4)?,  36);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // -180 should be the same as +180
+    #[test]
+    fn test_kite_angle_m180() {
+        let k = Kite::new(0.,0.,-180);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_angle(1)?, 144);
+            assert_eq!(k.edge_angle(2)?, 252);
+            assert_eq!(k.edge_angle(3)?, 288);
+            assert_eq!(k.edge_angle(4)?,  36);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_edge_slopes() {
+        let k = Kite::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(angle_func(k.edge_points(1)?), k.edge_angle(1)?);
+            assert_eq!(angle_func(k.edge_points(2)?), k.edge_angle(2)?);
+            assert_eq!(angle_func(k.edge_points(3)?), k.edge_angle(3)?);
+            assert_eq!(angle_func(k.edge_points(4)?), k.edge_angle(4)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_edge_lengths() {
+        let k = Kite::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(k.edge_length(1)?, EdgeLength::LONG);
+            assert_eq!(k.edge_length(2)?, EdgeLength::SHORT);
+            assert_eq!(k.edge_length(3)?, EdgeLength::SHORT);
+            assert_eq!(k.edge_length(4)?, EdgeLength::LONG);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_kite_edge_centers() {
+        let k = Kite::new(0.,0.,0);
+        let check_angles = || -> Result<(), i32> {
+            assert_eq!(midpt_func(k.edge_points(1)?), k.edge_center(1)?);
+            assert_eq!(midpt_func(k.edge_points(2)?), k.edge_center(2)?);
+            assert_eq!(midpt_func(k.edge_points(3)?), k.edge_center(3)?);
+            assert_eq!(midpt_func(k.edge_points(4)?), k.edge_center(4)?);
+            Ok(())
+        };
+        if let Err(_) = check_angles() {
+            assert!(false);
+        }
+    }
+
+    // PenroseTile tests
+    fn all_tiles(x: f64, y: f64, a: i32) -> Vec<Box<dyn PenroseTile>> {
+        vec![Box::new(Dart::new(x, y, a)),
+             Box::new(Kite::new(x, y, a)),
+             Box::new(Fat::new(x, y, a)),
+             Box::new(Skinny::new(x, y, a)),
+             Box::new(RobinsonA::new(x, y, a)),
+             Box::new(RobinsonA::new(x, y, a).mirror()),
+             Box::new(RobinsonB::new(x, y, a)),
+             Box::new(RobinsonB::new(x, y, a).mirror())]
+    }
+
+    #[test]
+    fn test_tile_kinds() {
+        let kinds: Vec<Tile> = all_tiles(0., 0., 0).iter().map(|t| t.kind()).collect();
+        assert_eq!(kinds, vec![Tile::DART, Tile::KITE, Tile::FAT, Tile::SKINNY,
+                               Tile::ROBINSON_A, Tile::ROBINSON_A_MIRROR, Tile::ROBINSON_B, Tile::ROBINSON_B_MIRROR]);
+        for t in all_tiles(0., 0., 0) {
+            assert_eq!(t.kind().is_triangle(), t.vertices().len() == 3);
+            assert_eq!(new_tile(t.kind(), 0., 0., 0).kind(), t.kind());
+        }
+    }
+
+    #[test]
+    fn test_tile_interior_angles() {
+        for t in all_tiles(1., 2., 108) {
+            let internal_angles = t.interior_angles();
+            let n = internal_angles.len() as i32;
+            assert_eq!(internal_angles.iter().sum::<i32>(), (n - 2) * 180);
+            let check_angles = || -> Result<(), i32> {
+                for e in 1..n+1 {
+                    let v = (e%n) as usize;
+                    assert_eq!((t.edge_angle(e)? + (540 - internal_angles[v]))%360, t.edge_angle(e%n + 1)?);
+                }
+                Ok(())
+            };
+            if let Err(_) = check_angles() {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tile_edge_slopes() {
+        for t in all_tiles(-3., 1., 0) {
+            let check_angles = || -> Result<(), i32> {
+                for e in 1..t.vertices().len() as i32 + 1 {
+                    assert_eq!(angle_func(t.edge_points(e)?), t.edge_angle(e)?);
+                    assert_eq!(midpt_func(t.edge_points(e)?), t.edge_center(e)?);
+                }
+                Ok(())
+            };
+            if let Err(_) = check_angles() {
+                assert!(false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tile_get_edges() {
+        for t in all_tiles(0., 0., 36) {
+            let edges = t.get_edges();
+            assert_eq!(edges.len(), t.vertices().len());
+            for (i, e) in edges.iter().enumerate() {
+                let n = i as i32 + 1;
+                assert_eq!(Ok(e.angle), t.edge_angle(n));
+                assert_eq!(Ok(e.length), t.edge_length(n));
+                assert_eq!(Ok(e.center), t.edge_center(n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reflected_tiles() {
+        let c = ExactPoint::new(Cyclotomic::new(1, 0, -2, 1)).half();
+        for kind in [Tile::DART, Tile::KITE, Tile::FAT, Tile::SKINNY].iter() {
+            let t = new_exact_tile(*kind, c, 72);
+            let r = reflect_tile(t.as_ref());
+            assert!(r.reflected() && !t.reflected());
+            assert!(!reflect_tile(r.as_ref()).reflected());
+            assert!(copy_tile(r.as_ref()).reflected());
+
+            // Mirror in the line through the centre at 72 degrees.
+            let (cx, cy) = t.center();
+            let (s, co) = 144_f64.to_radians().sin_cos();
+            let mirror = |p: (f64, f64)| (cx + co*(p.0 - cx) + s*(p.1 - cy), cy + s*(p.0 - cx) - co*(p.1 - cy));
+
+            // The vertices are mirrored, still run anticlockwise and are
+            // known exactly.
+            let (v, w) = (t.vertices(), r.vertices());
+            let n = v.len();
+            for i in 0..n {
+                assert!(dist_func(w[i], mirror(v[(n - i) % n])) < 1e-9);
+                assert_eq!(r.interior_angles()[i], t.interior_angles()[(n - i) % n]);
+            }
+            let area: f64 = (0..n).map(|i| w[i].0 * w[(i+1)%n].1 - w[(i+1)%n].0 * w[i].1).sum();
+            assert!(area > 0.);
+            for (p, q) in r.exact_vertices().unwrap().iter().zip(w.iter()) {
+                assert!(dist_func(p.to_f64(), *q) < 1e-12);
+            }
+
+            // Edge e is edge n+1-e run backwards, and agrees with the vertices.
+            let internal_angles = r.interior_angles();
+            for e in 1..n as i32 + 1 {
+                let m = n as i32 + 1 - e;
+                let (p, q) = r.edge_points(e).unwrap();
+                let a = (r.edge_angle(e).unwrap() as f64).to_radians();
+                assert!(dist_func(q, (p.0 + a.cos() * dist_func(p, q), p.1 + a.sin() * dist_func(p, q))) < 1e-9);
+                assert_eq!(r.edge_length(e), t.edge_length(m));
+                let (m1, m2) = t.edge_marks(m).unwrap();
+                assert_eq!(r.edge_marks(e), Ok((m2, m1)));
+                let v = (e % n as i32) as usize;
+                assert_eq!((r.edge_angle(e).unwrap() + (540 - internal_angles[v])) % 360,
+                           r.edge_angle(e % n as i32 + 1).unwrap());
+            }
+            assert_eq!(r.edge_angle(0), Err(0));
+            assert_eq!(r.edge_length(5), Err(5));
+
+            // The arcs and bars are mirrored as well.
+            for (a, b) in [(t.get_big_arc(), r.get_big_arc()), (t.get_small_arc(), r.get_small_arc())].iter() {
+                let end = |arc: &Arc, angle: i32| {
+                    let x = (angle as f64).to_radians();
+                    (arc.center.0 + arc.radius * x.cos(), arc.center.1 + arc.radius * x.sin())
+                };
+                assert!(dist_func(end(b, b.start_angle), mirror(end(a, a.end_angle))) < 1e-9);
+                assert!(dist_func(end(b, b.end_angle), mirror(end(a, a.start_angle))) < 1e-9);
+                assert_eq!((b.end_angle - b.start_angle).rem_euclid(360), (a.end_angle - a.start_angle).rem_euclid(360));
+            }
+            for (p, q) in t.get_ammann_bars().iter().zip(r.get_ammann_bars()) {
+                assert!(dist_func(mirror(*p), q) < 1e-9);
+            }
+
+            // Kites, darts and fat rhombi are symmetric about that line.
+            let same = w.iter().zip(v.iter()).all(|(p, q)| dist_func(*p, *q) < 1e-9);
+            assert_eq!(same, *kind != Tile::SKINNY);
+        }
+        assert!(Dart::new(0., 0., 0).mirror().rotate(36).translate(1., 1.).reflected());
+
+        // Triangles turn into their other halves.
+        let a = RobinsonA::new(1., 2., 144);
+        let b = reflect_tile(&a);
+        assert_eq!(b.kind(), Tile::ROBINSON_A_MIRROR);
+        assert!(!b.reflected());
+        assert_eq!(reflect_tile(b.as_ref()).kind(), Tile::ROBINSON_A);
+    }
+
+    // Exact coordinate tests
+    #[test]
+    fn test_exact_vertices_match_float() {
+        let c = ExactPoint::new(Cyclotomic::new(3, -1, 2, 5)).half();
+        for t in all_tiles(0., 0., 0) {
+            let t = new_exact_tile(t.kind(), c, 36);
+            let exact = t.exact_vertices().unwrap();
+            for (p, q) in exact.iter().zip(t.vertices()) {
+                assert!(dist_func(p.to_f64(), q) < 1e-12);
+            }
+        }
+        assert!(Dart::new(0., 0., 0).exact_vertices().is_none());
+        assert!(Dart::new_exact(c, 0).translate(1., 0.).exact_vertices().is_none());
+    }
+
+    #[test]
+    fn test_robinson_halves() {
+        let c = ExactPoint::new(Cyclotomic::new(1, 2, 0, -1));
+        for (whole, half) in [(Tile::KITE, Tile::ROBINSON_A), (Tile::DART, Tile::ROBINSON_B)].iter() {
+            let t = new_exact_tile(*whole, c, 72);
+            let h1 = new_exact_tile(*half, c, 72);
+            let h2: Box<dyn PenroseTile> = match half {
+                Tile::ROBINSON_A => Box::new(RobinsonA::new_exact(c, 72).mirror()),
+                _ => Box::new(RobinsonB::new_exact(c, 72).mirror()),
+            };
+            let v = t.exact_vertices().unwrap();
+            assert_eq!(h1.exact_vertices().unwrap(), vec![v[0], v[1], v[2]]);
+            assert_eq!(h2.exact_vertices().unwrap(), vec![v[0], v[2], v[3]]);
+
+            // The outer edges and the arcs of the halves are those of the whole.
+            assert_eq!(h1.edge_angle(1), t.edge_angle(1));
+            assert_eq!(h1.edge_angle(2), t.edge_angle(2));
+            assert_eq!(h2.edge_angle(2), t.edge_angle(3));
+            assert_eq!(h2.edge_angle(3), t.edge_angle(4));
+            assert_eq!(h1.edge_angle(3).unwrap(), (h2.edge_angle(1).unwrap() + 180) % 360);
+            for (a, b1, b2) in [(t.get_big_arc(), h1.get_big_arc(), h2.get_big_arc()),
+                                (t.get_small_arc(), h1.get_small_arc(), h2.get_small_arc())].iter() {
+                assert!(dist_func(a.center, b1.center) < 1e-12 && dist_func(a.center, b2.center) < 1e-12);
+                assert_eq!((a.radius, a.radius), (b1.radius, b2.radius));
+                let (first, second) = if a.start_angle == b1.start_angle { (b1, b2) } else { (b2, b1) };
+                assert_eq!(a.start_angle, first.start_angle);
+                assert_eq!(first.end_angle, second.start_angle);
+                assert_eq!(second.end_angle, a.end_angle);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_placement_matches_float() {
+        let d = Dart::new(0., 0., 0);
+        let k = Kite::new(0., 0., 0);
+        let dx = Dart::new_exact(ExactPoint::origin(), 0);
+        let kx = Kite::new_exact(ExactPoint::origin(), 0);
+        let check_placements = || -> Result<(), i32> {
+            for e in 1..5 {
+                for f in 1..5 {
+                    if d.edge_length(e)? == k.edge_length(f)? {
+                        let t1 = place_kite_edge(f, d.edge_center(e)?, d.edge_angle(e)?);
+                        let t2 = place_kite_edge_exact(f, dx.exact_edge_center(e).unwrap(), d.edge_angle(e)?).unwrap();
+                        assert!(dist_func((t1.cx, t1.cy), (t2.cx, t2.cy)) < 5e-8);
+                        assert_eq!(t1.angle(), t2.angle());
+                        assert!(edges_match_exact(&dx, e, &t2, f));
+                    }
+                    if k.edge_length(e)? == d.edge_length(f)? {
+                        let t1 = place_dart_edge(f, k.edge_center(e)?, k.edge_angle(e)?);
+                        let t2 = place_dart_edge_exact(f, kx.exact_edge_center(e).unwrap(), k.edge_angle(e)?).unwrap();
+                        assert!(dist_func((t1.cx, t1.cy), (t2.cx, t2.cy)) < 5e-8);
+                        assert_eq!(t1.angle(), t2.angle());
+                        assert!(edges_match_exact(&kx, e, &t2, f));
+                    }
+                }
+            }
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_exact_long_walk() {
+        // Walk thousands of tiles away from the origin, gluing each new tile
+        // to a short edge of the previous one, and check the glued edges
+        // still coincide exactly.
+        let short_edges = |t: &Tile| match t {
+            Tile::DART => vec![1, 4],
+            Tile::KITE => vec![2, 3],
+            _ => vec![1, 2, 3, 4],
+        };
+        let kinds = [Tile::FAT, Tile::SKINNY, Tile::KITE, Tile::DART];
+
+        let mut prev: Box<dyn PenroseTile> = Box::new(Fat::new_exact(ExactPoint::origin(), 0));
+        for i in 0..5000 {
+            let kind = kinds[i % 4];
+            let out_edges = short_edges(&prev.kind());
+            let out = out_edges[(i / 4) % out_edges.len()];
+            let in_edges = short_edges(&kind);
+            let inn = in_edges[(i / 3) % in_edges.len()];
+
+            let pt = prev.exact_edge_center(out).unwrap();
+            let a = prev.edge_angle(out).unwrap();
+            let next: Box<dyn PenroseTile> = match kind {
+                Tile::DART => Box::new(place_dart_edge_exact(inn, pt, a).unwrap()),
+                Tile::KITE => Box::new(place_kite_edge_exact(inn, pt, a).unwrap()),
+                Tile::FAT => Box::new(place_fat_edge_exact(inn, pt, a).unwrap()),
+                Tile::SKINNY => Box::new(place_skinny_edge_exact(inn, pt, a).unwrap()),
+                _ => unreachable!(),
+            };
+            assert!(edges_match_exact(prev.as_ref(), out, next.as_ref(), inn));
+            for (p, q) in next.exact_vertices().unwrap().iter().zip(next.vertices()) {
+                assert!(dist_func(p.to_f64(), q) < 1e-6);
+            }
+            prev = next;
+        }
+    }
+
+    //////////////////////////////////////
+    #[test]
+    fn test_vertex1() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+
+        let d1 = Dart::new(-phi, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let d2 = place_dart_edge(3, d1.edge_center(2)?, d1.edge_angle(2)?);
+            let d3 = place_dart_edge(3, d2.edge_center(2)?, d2.edge_angle(2)?);
+            let d4 = place_dart_edge(3, d3.edge_center(2)?, d3.edge_angle(2)?);
+            let d5 = place_dart_edge(3, d4.edge_center(2)?, d4.edge_angle(2)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((d1.cx, d1.cy), (-phi, 0.)) < 5e-8);
+            assert_eq!(d1.angle(), 0);
+
+            assert!(dist_func((d2.cx, d2.cy), (-0.5,-h)) < 5e-8);
+            assert_eq!(d2.angle(), 72);
+
+            assert!(dist_func((d3.cx, d3.cy), (k,-p)) < 5e-8);
+            assert_eq!(d3.angle(), 144);
+
+            assert!(dist_func((d4.cx, d4.cy), (k,p)) < 5e-8);
+            assert_eq!(d4.angle(), 216);
+
+            assert!(dist_func((d5.cx, d5.cy), (-0.5,h)) < 5e-8);
+            assert_eq!(d5.angle(), 288);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex2() {
+        let d1 = Dart::new(0., 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let k1 = place_kite_edge(2, d1.edge_center(4)?, d1.edge_angle(4)?);
+            let k2 = place_kite_edge(4, k1.edge_center(1)?, k1.edge_angle(1)?);
+
+            let s5 = 5_f64.sqrt();
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((d1.cx, d1.cy), (0., 0.)) < 5e-8);
+            assert_eq!(d1.angle(), 0);
+
+            assert!(dist_func((k1.cx, k1.cy), (-k, p)) < 5e-8);
+            assert_eq!(k1.angle(), 36);
+
+            assert!(dist_func((k2.cx, k2.cy), (-k,-p)) < 5e-8);
+            assert_eq!(k2.angle(), 324);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex3() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+
+        let k1 = Kite::new(phi, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let k2 = place_kite_edge(1, k1.edge_center(4)?, k1.edge_angle(4)?);
+            let k3 = place_kite_edge(1, k2.edge_center(4)?, k2.edge_angle(4)?);
+            let k4 = place_kite_edge(1, k3.edge_center(4)?, k3.edge_angle(4)?);
+            let k5 = place_kite_edge(1, k4.edge_center(4)?, k4.edge_angle(4)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((k1.cx, k1.cy), (phi, 0.)) < 5e-8);
+            assert_eq!(k1.angle(), 0);
+
+            assert!(dist_func((k2.cx, k2.cy), (0.5,h)) < 5e-8);
+            assert_eq!(k2.angle(), 72);
+
+            assert!(dist_func((k3.cx, k3.cy), (-k,p)) < 5e-8);
+            assert_eq!(k3.angle(), 144);
+
+            assert!(dist_func((k4.cx, k4.cy), (-k,-p)) < 5e-8);
+            assert_eq!(k4.angle(), 216);
+
+            assert!(dist_func((k5.cx, k5.cy), (0.5,-h)) < 5e-8);
+            assert_eq!(k5.angle(), 288);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex4() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+
+        let d1 = Dart::new(-phi, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let d2 = place_dart_edge(3, d1.edge_center(2)?, d1.edge_angle(2)?);
+            let k1 = place_kite_edge(1, d2.edge_center(2)?, d2.edge_angle(2)?);
+            let k2 = place_kite_edge(1, k1.edge_center(4)?, k1.edge_angle(4)?);
+            let d3 = place_dart_edge(3, k2.edge_center(4)?, k2.edge_angle(4)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((d1.cx, d1.cy), (-phi, 0.)) < 5e-8);
+            assert_eq!(d1.angle(), 0);
+
+            assert!(dist_func((d2.cx, d2.cy), (-0.5,-h)) < 5e-8);
+            assert_eq!(d2.angle(), 72);
+
+            assert!(dist_func((k1.cx, k1.cy), (k,-p)) < 5e-8);
+            assert_eq!(k1.angle(), 324);
+
+            assert!(dist_func((k2.cx, k2.cy), (k,p)) < 5e-8);
+            assert_eq!(k2.angle(), 36);
+
+            assert!(dist_func((d3.cx, d3.cy), (-0.5,h)) < 5e-8);
+            assert_eq!(d3.angle(), 288);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex5() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+
+        let k1 = Kite::new(-1., 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let d1 = place_dart_edge(4, k1.edge_center(2)?, k1.edge_angle(2)?);
+            let k2 = place_kite_edge(1, d1.edge_center(3)?, d1.edge_angle(3)?);
+            let k3 = place_kite_edge(1, k2.edge_center(4)?, k2.edge_angle(4)?);
+            let d2 = place_dart_edge(2, k3.edge_center(4)?, k3.edge_angle(4)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((k1.cx, k1.cy), (-1., 0.)) < 5e-8);
+            assert_eq!(k1.angle(), 0);
+
+            assert!(dist_func((d1.cx, d1.cy), (-0.5,-h)) < 5e-8);
+            assert_eq!(d1.angle(), 324);
+
+            assert!(dist_func((k2.cx, k2.cy), (k,-p)) < 5e-8);
+            assert_eq!(k2.angle(), 324);
+
+            assert!(dist_func((k3.cx, k3.cy), (k,p)) < 5e-8);
+            assert_eq!(k3.angle(), 36);
+
+            assert!(dist_func((d2.cx, d2.cy), (-0.5,h)) < 5e-8);
+            assert_eq!(d2.angle(), 36);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex6() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+
+        let d1 = Dart::new(-phi, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let k1 = place_kite_edge(4, d1.edge_center(2)?, d1.edge_angle(2)?);
+            let k2 = place_kite_edge(2, k1.edge_center(3)?, k1.edge_angle(3)?);
+            let k3 = place_kite_edge(4, k2.edge_center(1)?, k2.edge_angle(1)?);
+            let k4 = place_kite_edge(2, k3.edge_center(3)?, k3.edge_angle(3)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+            let k = (2.+s5) / (1.+s5);
+            let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+            assert!(dist_func((d1.cx, d1.cy), (-phi, 0.)) < 5e-8);
+            assert_eq!(d1.angle(), 0);
+
+            assert!(dist_func((k1.cx, k1.cy), (-0.5,-h)) < 5e-8);
+            assert_eq!(k1.angle(), 0);
+
+            assert!(dist_func((k2.cx, k2.cy), (k,-p)) < 5e-8);
+            assert_eq!(k2.angle(), 216);
+
+            assert!(dist_func((k3.cx, k3.cy), (k, p)) < 5e-8);
+            assert_eq!(k3.angle(), 144);
+
+            assert!(dist_func((k4.cx, k4.cy), (-0.5, h)) < 5e-8);
+            assert_eq!(k4.angle(), 0);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex7() {
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let k = (2.+s5) / (1.+s5);
+        let p = (10. + (20. as f64).sqrt()).sqrt()/4.;
+
+        let k1 = Kite::new(k-1.,-p, 108);
+        let check_placements = || -> Result<(), i32> {
+            let k2 = place_kite_edge(3, k1.edge_center(2)?, k1.edge_angle(2)?);
+            let d1 = place_dart_edge(4, k2.edge_center(2)?, k2.edge_angle(2)?);
+            let d2 = place_dart_edge(2, d1.edge_center(3)?, d1.edge_angle(3)?);
+
+            let h = (5.+2.*s5).sqrt()/2.;
+
+            assert!(dist_func((k1.cx, k1.cy), (k-1.,-p)) < 5e-8);
+            assert_eq!(k1.angle(), 108);
+
+            assert!(dist_func((k2.cx, k2.cy), (k-1., p)) < 5e-8);
+            assert_eq!(k2.angle(), 252);
+
+            assert!(dist_func((d1.cx, d1.cy), (-k, p)) < 5e-8);
+            assert_eq!(d1.angle(), 216);
+
+            assert!(dist_func((d2.cx, d2.cy), (-k,-p)) < 5e-8);
+            assert_eq!(d2.angle(), 144);
+
+            Ok(())
+        };
+        if let Err(_) = check_placements() {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_vertex8() {
+        // Five fat rhombi round their big arcs.
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let r = phi*phi/2.;
+
+        let f1 = Fat::new(r, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let f2 = place_fat_edge(1, f1.edge_center(4)?, f1.edge_angle(4)?);
+            let f3 = place_fat_edge(1, f2.edge_center(4)?, f2.edge_angle(4)?);
+            let f4 = place_fat_edge(1, f3.edge_center(4)?, f3.edge_angle(4)?);
+            let f5 = place_fat_edge(1, f4.edge_center(4)?, f4.edge_angle(4)?);
+
+            for (i, f) in [&f2, &f3, &f4, &f5].iter().enumerate() {
+                let a = (72 * (i + 1)) as f64 * std::f64::consts::PI / 180.;
+                assert!(dist_func((f.cx, f.cy), (r*a.cos(), r*a.sin())) < 5e-8);
+                assert_eq!(f.angle(), 72 * (i as i32 + 1));
+            }
+            Ok(())
+        };
+        check_placements().unwrap();
+    }
+
+    #[test]
+    fn test_vertex9() {
+        // A fat rhombus at its small arc between two skinny ones.
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let r = phi*phi/2.;
+
+        let f1 = Fat::new(-r, 0., 0);
+        let check_placements = || -> Result<(), i32> {
+            let s1 = place_skinny_edge(4, f1.edge_center(2)?, f1.edge_angle(2)?);
+            let s2 = place_skinny_edge(4, s1.edge_center(3)?, s1.edge_angle(3)?);
+
+            // The skinny rhombi meet the vertex at their blunt corners, so
+            // their centres are half a short diagonal out, at 72 degrees
+            // either side.
+            let d = phi * (18_f64 * std::f64::consts::PI / 180.).sin();
+            let t = 72_f64 * std::f64::consts::PI / 180.;
+            assert!(dist_func((s1.cx, s1.cy), (d*t.cos(), -d*t.sin())) < 5e-8);
+            assert_eq!(s1.angle(), 0);
+            assert!(dist_func((s2.cx, s2.cy), (d*t.cos(), d*t.sin())) < 5e-8);
+            assert_eq!(s2.angle(), 144);
+            Ok(())
+        };
+        check_placements().unwrap();
+    }
+
+    #[test]
+    fn test_place_edge() {
+        // Every edge of every kind lands where the exact placement puts it,
+        // lying along the edge it was placed on.
+        let kinds = [Tile::DART, Tile::KITE, Tile::FAT, Tile::SKINNY,
+                     Tile::ROBINSON_A, Tile::ROBINSON_A_MIRROR, Tile::ROBINSON_B, Tile::ROBINSON_B_MIRROR];
+        let base = Kite::new_exact(ExactPoint::origin(), 36);
+        for kind in kinds.iter() {
+            let n = new_tile(*kind, 0., 0., 0).vertices().len() as i32;
+            for e in 1..=n {
+                for on in 1..5 {
+                    let pt = base.edge_center(on).unwrap();
+                    let angle = base.edge_angle(on).unwrap();
+                    let t = place_edge(*kind, e, pt, angle);
+                    let (c, a) = exact_edge_placement(*kind, e, base.exact_edge_center(on).unwrap(), angle).unwrap();
+                    assert_eq!(t.kind(), *kind);
+                    assert_eq!(t.angle(), a);
+                    assert!(dist_func(t.center(), c.to_f64()) < 5e-8);
+                    assert!(dist_func(t.edge_center(e).unwrap(), pt) < 5e-8);
+                    assert_eq!(t.edge_angle(e).unwrap(), (angle + 180) % 360);
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_ammann_bars() {
+        // Whole tiles have bars from edge to edge; triangles have none.
+        for (kind, n) in [(Tile::DART, 8), (Tile::KITE, 6), (Tile::FAT, 4), (Tile::SKINNY, 4),
+                          (Tile::ROBINSON_A, 0), (Tile::ROBINSON_B, 0)].iter() {
+            for angle in (0..360).step_by(36) {
+                let t = new_tile(*kind, 1.5, -2., angle);
+                let bars = t.get_ammann_bars();
+                assert_eq!(bars.len(), *n);
+                for p in bars.iter() {
+                    assert!((1..=t.vertices().len() as i32).any(|e| {
+                        let (a, b) = t.edge_points(e).unwrap();
+                        ((a.0 - p.0).hypot(a.1 - p.1) + (b.0 - p.0).hypot(b.1 - p.1) - (a.0 - b.0).hypot(a.1 - b.1)).abs() < 1e-9
+                    }));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Placing a whole patch in the plane.
+//!
+//! Tiles are laid out in the patch's own frame, where they meet exactly at
+//! multiples of 36 degrees. A `RigidTransform` then puts the patch anywhere,
+//! turned by any angle or mirrored, for drawing and export, without touching
+//! the tiles, so the matching logic never sees a rounded angle.
+
+use crate::tiles::*;
+
+/// A mirror in the x axis if `reflected`, then a turn by `rotation` degrees
+/// anticlockwise about the origin, then a shift by `translation`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct RigidTransform {
+    pub rotation: f64,
+    pub reflected: bool,
+    pub translation: (f64, f64),
+}
+
+impl Default for RigidTransform {
+    fn default() -> Self {
+        RigidTransform::identity()
+    }
+}
+
+impl RigidTransform {
+    pub fn identity() -> Self {
+        RigidTransform { rotation: 0., reflected: false, translation: (0., 0.) }
+    }
+
+    pub fn rotation(degrees: f64) -> Self {
+        RigidTransform { rotation: degrees, ..RigidTransform::identity() }
+    }
+
+    pub fn translation(dx: f64, dy: f64) -> Self {
+        RigidTransform { translation: (dx, dy), ..RigidTransform::identity() }
+    }
+
+    /// A mirror in the line through the origin at `degrees`.
+    pub fn reflection(degrees: f64) -> Self {
+        RigidTransform { rotation: 2. * degrees, reflected: true, translation: (0., 0.) }
+    }
+
+    /// A turn by `degrees` that leaves `p` where it is.
+    pub fn rotation_about(p: (f64, f64), degrees: f64) -> Self {
+        RigidTransform::translation(-p.0, -p.1)
+            .then(&RigidTransform::rotation(degrees))
+            .then(&RigidTransform::translation(p.0, p.1))
+    }
+
+    /// A mirror in the line through `p` at `degrees`.
+    pub fn reflection_about(p: (f64, f64), degrees: f64) -> Self {
+        RigidTransform::translation(-p.0, -p.1)
+            .then(&RigidTransform::reflection(degrees))
+            .then(&RigidTransform::translation(p.0, p.1))
+    }
+
+    pub fn apply(&self, p: (f64, f64)) -> (f64, f64) {
+        let (s, c) = self.rotation.to_radians().sin_cos();
+        let p = if self.reflected { (p.0, -p.1) } else { p };
+        (c*p.0 - s*p.1 + self.translation.0, s*p.0 + c*p.1 + self.translation.1)
+    }
+
+    pub fn inverse(&self) -> Self {
+        // A mirror turns the other way round, so undoing it is itself.
+        let rotation = if self.reflected { self.rotation } else { -self.rotation };
+        let back = RigidTransform { rotation, reflected: self.reflected, translation: (0., 0.) };
+        let t = back.apply(self.translation);
+        RigidTransform { translation: (-t.0, -t.1), ..back }
+    }
+
+    /// This transform followed by `other`.
+    pub fn then(&self, other: &RigidTransform) -> Self {
+        // Mirroring after a turn is mirroring before the opposite turn.
+        let rotation = if other.reflected { other.rotation - self.rotation } else { other.rotation + self.rotation };
+        RigidTransform {
+            rotation: rotation.rem_euclid(360.),
+            reflected: self.reflected != other.reflected,
+            translation: other.apply(self.translation),
+        }
+    }
+
+    /// The direction, in degrees, that `angle` in the patch points in.
+    pub fn direction(&self, angle: f64) -> f64 {
+        let angle = if self.reflected { -angle } else { angle };
+        (angle + self.rotation).rem_euclid(360.)
+    }
+
+    /// Where the vertices of `t` end up.
+    pub fn vertices(&self, t: &dyn PenroseTile) -> Vec<(f64, f64)> {
+        t.vertices().iter().map(|p| self.apply(*p)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(p: (f64, f64), q: (f64, f64)) -> bool {
+        (p.0 - q.0).hypot(p.1 - q.1) < 1e-9
+    }
+
+    #[test]
+    fn test_rigid_transform() {
+        let a = RigidTransform { rotation: 23.5, reflected: false, translation: (1., -2.) };
+        let b = RigidTransform::rotation_about((3., 4.), -100.);
+        let m = RigidTransform { rotation: -61., reflected: true, translation: (-0.5, 3.) };
+        let p = (0.7, 5.2);
+        for t in [a, b, m].iter() {
+            assert!(close(t.inverse().apply(t.apply(p)), p));
+            assert!(close(t.then(&t.inverse()).apply(p), p));
+            for u in [a, b, m].iter() {
+                assert!(close(t.then(u).apply(p), u.apply(t.apply(p))));
+            }
+        }
+        assert!(close(b.apply((3., 4.)), (3., 4.)));
+        assert!(close(RigidTransform::rotation(90.).apply((1., 0.)), (0., 1.)));
+        assert!(close(RigidTransform::default().apply(p), p));
+
+        // A mirror keeps the points on its line, and twice is nothing.
+        let r = RigidTransform::reflection_about((1., 1.), 45.);
+        assert!(close(r.apply((3., 3.)), (3., 3.)));
+        assert!(close(r.apply((1., 0.)), (0., 1.)));
+        assert!(close(r.then(&r).apply(p), p));
+
+        // Distances are kept.
+        let q = (-1., 2.);
+        let d = |p: (f64, f64), q: (f64, f64)| (p.0 - q.0).hypot(p.1 - q.1);
+        assert!((d(a.apply(p), a.apply(q)) - d(p, q)).abs() < 1e-9);
+        assert!((d(m.apply(p), m.apply(q)) - d(p, q)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_turned_tiles() {
+        // A dart turned 90 degrees, or -270, as a whole.
+        let d = Dart::new(0., 0., 0);
+        for rotation in [90., -270.].iter() {
+            let t = RigidTransform::rotation(*rotation);
+            let directions: Vec<f64> = (1..5).map(|e| t.direction(d.edge_angle(e).unwrap() as f64)).collect();
+            assert_eq!(directions, vec![342., 126., 234., 18.]);
+        }
+
+        // Mirrored in the y axis, edges point back the other way.
+        let t = RigidTransform::reflection(90.);
+        let directions: Vec<f64> = (1..5).map(|e| t.direction(d.edge_angle(e).unwrap() as f64)).collect();
+        assert_eq!(directions, vec![288., 144., 36., 252.]);
+
+        // Turning a patch by 36 degrees matches turning its tiles.
+        let t = RigidTransform::rotation_about((0.5, -1.), 36.);
+        let k = Kite::new(2., 1., 72);
+        let c = t.apply(k.center());
+        let turned = Kite::new(c.0, c.1, 108);
+        for (p, q) in t.vertices(&k).iter().zip(turned.vertices()) {
+            assert!(close(*p, q));
+        }
+    }
+}
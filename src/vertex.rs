@@ -0,0 +1,356 @@
+//! The kinds of vertex in a Penrose tiling.
+//!
+//! A kite and dart tiling has seven, which Conway named sun, star, ace,
+//! deuce, jack, queen and king. A rhombus tiling has eight. Each is built by
+//! gluing tiles edge to edge around the vertex, the same way as in the
+//! `test_vertex` tests, and is then moved so the vertex is where it was
+//! asked for. The king differs from `test_vertex4`, whose second dart and
+//! first kite meet against the matching rules.
+
+use crate::exact::ExactPoint;
+use crate::tiles::*;
+
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum VertexType {
+    /// Five kite tails.
+    Sun,
+    /// Five dart tips.
+    Star,
+    /// A dart's concave vertex between the sides of two kites.
+    Ace,
+    /// The heads of two kites between the sides of two darts.
+    Deuce,
+    /// A kite's head and two kite tails between the sides of two darts.
+    Jack,
+    /// A dart tip between the sides of four kites.
+    Queen,
+    /// Three dart tips between the sides of two kites.
+    King,
+}
+
+/// The vertices of a rhombus tiling, named by the sharp corners of fat
+/// rhombi that meet there and which arc those corners carry.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+pub enum RhombusVertexType {
+    /// Five fat rhombi meeting at their big arcs.
+    FiveBig,
+    /// Five fat rhombi meeting at their small arcs.
+    FiveSmall,
+    /// Four fat rhombi at their big arcs and the sharp corners of two
+    /// skinny ones.
+    FourBig,
+    /// Three fat rhombi at their big arcs and the sharp corners of four
+    /// skinny ones, in two pairs.
+    ThreeBig,
+    /// A fat rhombus at its big arc, two skinny sharp corners and two fat
+    /// blunt corners.
+    OneBig,
+    /// The blunt corners of two fat rhombi and of a skinny one at its big
+    /// arc.
+    Blunt,
+    /// Three fat rhombi at their small arcs and a skinny one at its small
+    /// arc.
+    ThreeSmall,
+    /// A fat rhombus at its small arc between two skinny ones at theirs.
+    OneSmall,
+}
+
+// A tile glued on as (kind, its edge, the index of the tile it is glued to,
+// that tile's edge).
+type Step = (Tile, i32, usize, i32);
+
+// Glue a tile on to edge `on` of `t`, by its own edge `e`.
+fn glue(kind: Tile, e: i32, t: &dyn PenroseTile, on: i32) -> Option<Box<dyn PenroseTile>> {
+    let pt = t.exact_edge_center(on)?;
+    let angle = t.edge_angle(on).ok()?;
+    match kind {
+        Tile::KITE => Some(Box::new(place_kite_edge_exact(e, pt, angle)?)),
+        Tile::DART => Some(Box::new(place_dart_edge_exact(e, pt, angle)?)),
+        Tile::FAT => Some(Box::new(place_fat_edge_exact(e, pt, angle)?)),
+        _ => Some(Box::new(place_skinny_edge_exact(e, pt, angle)?)),
+    }
+}
+
+// The tiles of a recipe, the first tile as kind and angle and then the rest
+// in order, with the vertex they share at the origin.
+fn tiles_at_origin(recipe: ((Tile, i32), Vec<Step>)) -> Vec<Box<dyn PenroseTile>> {
+    let ((kind, angle), rest) = recipe;
+    let mut tiles = vec![new_exact_tile(kind, ExactPoint::origin(), angle)];
+    for (kind, e, i, on) in rest {
+        let t = glue(kind, e, tiles[i].as_ref(), on).unwrap();
+        tiles.push(t);
+    }
+
+    // The vertex is the one point every tile has as a corner.
+    let shared = tiles[0].exact_vertices().unwrap().into_iter()
+        .find(|v| tiles.iter().all(|t| t.exact_vertices().unwrap().contains(v)))
+        .unwrap();
+    tiles.iter().map(|t| new_exact_tile(t.kind(), t.exact_center().unwrap() - shared, t.angle())).collect()
+}
+
+fn place_exact(tiles: &[Box<dyn PenroseTile>], p: ExactPoint, angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+    tiles.iter()
+        .map(|t| Some(new_exact_tile(t.kind(), p + t.exact_center()?.rotate(angle)?, t.angle() + angle)))
+        .collect()
+}
+
+// Tiles can only face multiples of 36 degrees, so other turns are refused
+// rather than leaving the centres turned further than the tiles.
+fn place(tiles: &[Box<dyn PenroseTile>], p: (f64, f64), angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+    if angle % 36 != 0 {
+        return None
+    }
+    let (s, c) = (angle as f64).to_radians().sin_cos();
+    Some(tiles.iter().map(|t| {
+        let (x, y) = t.center();
+        new_tile(t.kind(), p.0 + c*x - s*y, p.1 + s*x + c*y, t.angle() + angle)
+    }).collect())
+}
+
+impl VertexType {
+    pub fn all() -> [VertexType; 7] {
+        [VertexType::Sun, VertexType::Star, VertexType::Ace, VertexType::Deuce,
+         VertexType::Jack, VertexType::Queen, VertexType::King]
+    }
+
+    fn recipe(&self) -> ((Tile, i32), Vec<Step>) {
+        match self {
+            VertexType::Star => ((Tile::DART, 0),
+                                 (0..4).map(|i| (Tile::DART, 3, i, 2)).collect()),
+            VertexType::Ace => ((Tile::DART, 0),
+                                vec![(Tile::KITE, 2, 0, 4), (Tile::KITE, 4, 1, 1)]),
+            VertexType::Sun => ((Tile::KITE, 0),
+                                (0..4).map(|i| (Tile::KITE, 1, i, 4)).collect()),
+            VertexType::King => ((Tile::DART, 0),
+                                 vec![(Tile::DART, 3, 0, 2), (Tile::DART, 3, 1, 2),
+                                      (Tile::KITE, 4, 2, 2), (Tile::KITE, 2, 3, 3)]),
+            VertexType::Jack => ((Tile::KITE, 0),
+                                 vec![(Tile::DART, 4, 0, 2), (Tile::KITE, 1, 1, 3),
+                                      (Tile::KITE, 1, 2, 4), (Tile::DART, 2, 3, 4)]),
+            VertexType::Queen => ((Tile::DART, 0),
+                                  vec![(Tile::KITE, 4, 0, 2), (Tile::KITE, 2, 1, 3),
+                                       (Tile::KITE, 4, 2, 1), (Tile::KITE, 2, 3, 3)]),
+            VertexType::Deuce => ((Tile::KITE, 108),
+                                  vec![(Tile::KITE, 3, 0, 2), (Tile::DART, 4, 1, 2),
+                                       (Tile::DART, 2, 2, 3)]),
+        }
+    }
+
+    /// The tiles around a vertex of this type at `p`, turned by `angle`
+    /// degrees, which must be a multiple of 36.
+    pub fn tiles_exact(&self, p: ExactPoint, angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+        place_exact(&tiles_at_origin(self.recipe()), p, angle)
+    }
+
+    /// The tiles around a vertex of this type at `p`, turned by `angle`
+    /// degrees, which must be a multiple of 36.
+    pub fn tiles(&self, p: (f64, f64), angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+        place(&tiles_at_origin(self.recipe()), p, angle)
+    }
+}
+
+impl RhombusVertexType {
+    pub fn all() -> [RhombusVertexType; 8] {
+        [RhombusVertexType::FiveBig, RhombusVertexType::FiveSmall, RhombusVertexType::FourBig,
+         RhombusVertexType::ThreeBig, RhombusVertexType::OneBig, RhombusVertexType::Blunt,
+         RhombusVertexType::ThreeSmall, RhombusVertexType::OneSmall]
+    }
+
+    fn recipe(&self) -> ((Tile, i32), Vec<Step>) {
+        match self {
+            RhombusVertexType::FiveBig => ((Tile::FAT, 0),
+                                           (0..4).map(|i| (Tile::FAT, 1, i, 4)).collect()),
+            RhombusVertexType::FiveSmall => ((Tile::FAT, 0),
+                                             (0..4).map(|i| (Tile::FAT, 3, i, 2)).collect()),
+            RhombusVertexType::FourBig => ((Tile::FAT, 0),
+                                           vec![(Tile::FAT, 1, 0, 4), (Tile::FAT, 1, 1, 4), (Tile::FAT, 1, 2, 4),
+                                                (Tile::SKINNY, 1, 3, 4), (Tile::SKINNY, 3, 4, 4)]),
+            RhombusVertexType::ThreeBig => ((Tile::FAT, 0),
+                                            vec![(Tile::FAT, 1, 0, 4), (Tile::SKINNY, 1, 1, 4),
+                                                 (Tile::SKINNY, 3, 2, 4), (Tile::FAT, 1, 3, 2),
+                                                 (Tile::SKINNY, 1, 4, 4), (Tile::SKINNY, 3, 5, 4)]),
+            RhombusVertexType::OneBig => ((Tile::FAT, 0),
+                                          vec![(Tile::SKINNY, 1, 0, 4), (Tile::FAT, 2, 1, 4),
+                                               (Tile::FAT, 4, 2, 1), (Tile::SKINNY, 3, 3, 3)]),
+            RhombusVertexType::Blunt => ((Tile::FAT, 0),
+                                         vec![(Tile::SKINNY, 2, 0, 1), (Tile::FAT, 4, 1, 1)]),
+            RhombusVertexType::ThreeSmall => ((Tile::FAT, 0),
+                                              vec![(Tile::FAT, 3, 0, 2), (Tile::FAT, 3, 1, 2),
+                                                   (Tile::SKINNY, 4, 2, 2)]),
+            RhombusVertexType::OneSmall => ((Tile::FAT, 0),
+                                            vec![(Tile::SKINNY, 4, 0, 2), (Tile::SKINNY, 4, 1, 3)]),
+        }
+    }
+
+    /// The rhombi around a vertex of this type at `p`, turned by `angle`
+    /// degrees, which must be a multiple of 36.
+    pub fn tiles_exact(&self, p: ExactPoint, angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+        place_exact(&tiles_at_origin(self.recipe()), p, angle)
+    }
+
+    /// The rhombi around a vertex of this type at `p`, turned by `angle`
+    /// degrees, which must be a multiple of 36.
+    pub fn tiles(&self, p: (f64, f64), angle: i32) -> Option<Vec<Box<dyn PenroseTile>>> {
+        place(&tiles_at_origin(self.recipe()), p, angle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deflate::tests::*;
+    use crate::deflate::*;
+    use crate::pentagrid::*;
+    use crate::rules::*;
+    use std::collections::{HashMap, HashSet};
+
+    // The tile kinds meeting at each exact vertex, as sorted counts of
+    // kites and darts.
+    fn vertex_counts(tiles: &[Box<dyn PenroseTile>]) -> HashMap<ExactPoint, (usize, usize, i32)> {
+        let mut result = HashMap::new();
+        for t in tiles {
+            let angles = t.interior_angles();
+            for (v, a) in t.exact_vertices().unwrap().iter().zip(angles.iter()) {
+                let e = result.entry(*v).or_insert((0, 0, 0));
+                match t.kind() {
+                    Tile::KITE => e.0 += 1,
+                    _ => e.1 += 1,
+                }
+                e.2 += a;
+            }
+        }
+        result
+    }
+
+    // The sum of the tile angles at each exact vertex.
+    fn vertex_angles(tiles: &[Box<dyn PenroseTile>]) -> HashMap<ExactPoint, i32> {
+        vertex_counts(tiles).into_iter().map(|(p, (_, _, a))| (p, a)).collect()
+    }
+
+    // The sorted tile corners at `p`, which tell the rhombus vertex types
+    // apart.
+    fn corners_at(tiles: &[Box<dyn PenroseTile>], p: ExactPoint) -> Vec<(Tile, usize)> {
+        let mut result: Vec<(Tile, usize)> = tiles.iter()
+            .filter_map(|t| Some((t.kind(), t.exact_vertices()?.iter().position(|v| *v == p)?)))
+            .collect();
+        result.sort_by_key(|(kind, c)| (*kind == Tile::SKINNY, *c));
+        result
+    }
+
+    #[test]
+    fn test_vertex_types() {
+        let expected = [(VertexType::Sun, (5, 0)), (VertexType::Star, (0, 5)),
+                        (VertexType::Ace, (2, 1)), (VertexType::Deuce, (2, 2)),
+                        (VertexType::Jack, (3, 2)), (VertexType::Queen, (4, 1)),
+                        (VertexType::King, (2, 3))];
+        for (v, (kites, darts)) in expected.iter() {
+            for angle in [0, 36, 252].iter() {
+                let p = ExactPoint::new(crate::exact::Cyclotomic::new(2, -1, 0, 3));
+                let tiles = v.tiles_exact(p, *angle).unwrap();
+                assert_eq!(count(&tiles, Tile::KITE), *kites);
+                assert_eq!(count(&tiles, Tile::DART), *darts);
+                assert_eq!(vertex_counts(&tiles)[&p], (*kites, *darts, 360));
+                assert!(rule_violations(&tiles, 1e-6).is_empty());
+                check_no_overlaps(&tiles);
+                check_vertex_colours(&tiles);
+
+                // The float tiles are the same.
+                let float = v.tiles(p.to_f64(), *angle).unwrap();
+                for (e, f) in tiles.iter().zip(float.iter()) {
+                    assert_eq!(e.kind(), f.kind());
+                    assert_eq!(e.angle(), f.angle());
+                    assert!((e.center().0 - f.center().0).abs() < 1e-9);
+                    assert!((e.center().1 - f.center().1).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_vertex_types_occur() {
+        // Every vertex type turns up inside a deflated sun, and nothing else
+        // does.
+        let tiles = deflate_n(&sun(), 5);
+        let found: Vec<(usize, usize)> = vertex_counts(&tiles).values()
+            .filter(|(_, _, a)| *a == 360)
+            .map(|(k, d, _)| (*k, *d))
+            .collect();
+        for v in VertexType::all().iter() {
+            let tiles = v.tiles((0., 0.), 0).unwrap();
+            let kd = (count(&tiles, Tile::KITE), count(&tiles, Tile::DART));
+            assert!(found.contains(&kd));
+        }
+        assert!(found.iter().all(|kd| VertexType::all().iter().any(|v| {
+            let tiles = v.tiles((0., 0.), 0).unwrap();
+            (count(&tiles, Tile::KITE), count(&tiles, Tile::DART)) == *kd
+        })));
+    }
+
+    #[test]
+    fn test_rhombus_vertex_types() {
+        let expected = [(RhombusVertexType::FiveBig, (5, 0)), (RhombusVertexType::FiveSmall, (5, 0)),
+                        (RhombusVertexType::FourBig, (4, 2)), (RhombusVertexType::ThreeBig, (3, 4)),
+                        (RhombusVertexType::OneBig, (3, 2)), (RhombusVertexType::Blunt, (2, 1)),
+                        (RhombusVertexType::ThreeSmall, (3, 1)), (RhombusVertexType::OneSmall, (1, 2))];
+        for (v, (fat, skinny)) in expected.iter() {
+            for angle in [0, 36, 252].iter() {
+                let p = ExactPoint::new(crate::exact::Cyclotomic::new(2, -1, 0, 3));
+                let tiles = v.tiles_exact(p, *angle).unwrap();
+                assert_eq!(count(&tiles, Tile::FAT), *fat);
+                assert_eq!(count(&tiles, Tile::SKINNY), *skinny);
+                assert_eq!(vertex_angles(&tiles)[&p], 360);
+                assert!(rule_violations(&tiles, 1e-6).is_empty());
+                check_no_overlaps(&tiles);
+                check_rhombus_arcs(&tiles);
+
+                let float = v.tiles(p.to_f64(), *angle).unwrap();
+                for (e, f) in tiles.iter().zip(float.iter()) {
+                    assert_eq!(e.kind(), f.kind());
+                    assert_eq!(e.angle(), f.angle());
+                    assert!((e.center().0 - f.center().0).abs() < 1e-9);
+                    assert!((e.center().1 - f.center().1).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rhombus_vertex1() {
+        // Five fat rhombi round their big arcs, with centres half the long
+        // diagonal from the vertex.
+        let s5 = 5_f64.sqrt();
+        let phi = (1.+s5)/2.;
+        let tiles = RhombusVertexType::FiveBig.tiles((1., 2.), 0).unwrap();
+        for (i, t) in tiles.iter().enumerate() {
+            let a = (72 * i as i32) as f64;
+            let (s, c) = a.to_radians().sin_cos();
+            assert_eq!(t.kind(), Tile::FAT);
+            assert_eq!(t.angle(), (72 * i as i32) % 360);
+            assert!((t.center().0 - (1. + phi*phi/2. * c)).abs() < 5e-8);
+            assert!((t.center().1 - (2. + phi*phi/2. * s)).abs() < 5e-8);
+        }
+
+        // Tiles can't face other angles, so neither can the vertex.
+        assert!(RhombusVertexType::FiveBig.tiles((1., 2.), 90).is_none());
+        assert!(VertexType::Sun.tiles((1., 2.), -20).is_none());
+        assert!(VertexType::Sun.tiles((1., 2.), -36).is_some());
+    }
+
+    #[test]
+    fn test_rhombus_vertex_types_occur() {
+        // Every vertex type turns up in a pentagrid tiling, and nothing else
+        // does.
+        let tiles = pentagrid([0.1, 0.37, -0.24, 0.02, -0.25], &BoundingBox::around((0., 0.), 20.));
+        let mut found = HashSet::new();
+        for (p, a) in vertex_angles(&tiles) {
+            if a == 360 {
+                found.insert(corners_at(&tiles, p));
+            }
+        }
+        let expected: HashSet<Vec<(Tile, usize)>> = RhombusVertexType::all().iter()
+            .map(|v| corners_at(&v.tiles_exact(ExactPoint::origin(), 0).unwrap(), ExactPoint::origin()))
+            .collect();
+        assert_eq!(expected.len(), 8);
+        assert_eq!(found, expected);
+    }
+}